@@ -0,0 +1,537 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use console::{Key, Term};
+
+use crate::{
+    prompt::{
+        cursor::StringCursor,
+        interaction::{fit_input, Event, PromptInteraction, State, Timeout, TimeoutAction},
+    },
+    state::stored_answer,
+    theme::THEME,
+};
+
+type SuggestionCallback = Box<dyn Fn(&str) -> Vec<String>>;
+
+/// A prompt that accepts a single line of text input suggesting the matches
+/// filtered live while typing.
+///
+/// Arrow keys highlight a suggestion, `Tab` completes the input with it,
+/// and `Enter` submits either the highlighted suggestion or the typed text.
+///
+/// # Example
+///
+/// ```
+/// use cliclack::Autocomplete;
+///
+/// # fn test() -> std::io::Result<()> {
+/// let packages = ["serde", "serde_json", "tokio", "textwrap"];
+///
+/// let package = Autocomplete::new("Pick a package")
+///     .suggestions(move |query| {
+///         packages
+///             .iter()
+///             .filter(|package| package.starts_with(query))
+///             .map(|package| package.to_string())
+///             .collect()
+///     })
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
+pub struct Autocomplete {
+    prompt: String,
+    input: StringCursor,
+    placeholder: StringCursor,
+    prompt_id: Option<String>,
+    suggest: Option<SuggestionCallback>,
+    stored: Option<String>,
+    matches: Vec<String>,
+    query: Option<String>,
+    cursor: Option<usize>,
+    offset: usize,
+    max_items: usize,
+    no_cancel: bool,
+    flush_input: bool,
+    timeout: Timeout,
+    help: Option<String>,
+    preset: Option<String>,
+}
+
+impl Autocomplete {
+    /// Creates a new autocomplete prompt.
+    pub fn new(prompt: impl Display) -> Self {
+        Self {
+            prompt: prompt.to_string(),
+            input: StringCursor::default(),
+            placeholder: StringCursor::default(),
+            prompt_id: None,
+            suggest: None,
+            stored: None,
+            matches: Vec::new(),
+            query: None,
+            cursor: None,
+            offset: 0,
+            max_items: 5,
+            no_cancel: false,
+            flush_input: false,
+            timeout: Timeout::default(),
+            help: None,
+            preset: None,
+        }
+    }
+
+    /// Sets the placeholder (hint) text for the input.
+    pub fn placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder.extend(placeholder);
+        self
+    }
+
+    /// Sets the identifier of the prompt to look up the previous answer
+    /// with the global [`StateProvider`](crate::StateProvider).
+    ///
+    /// The stored answer is typed into the input beforehand, so it's
+    /// submitted on `Enter` or can be edited.
+    pub fn prompt_id(mut self, id: &str) -> Self {
+        self.prompt_id = Some(id.into());
+        self
+    }
+
+    /// Sets the source of the suggestions which is queried with the typed
+    /// text on every change.
+    pub fn suggestions<F>(mut self, suggest: F) -> Self
+    where
+        F: Fn(&str) -> Vec<String> + 'static,
+    {
+        self.suggest = Some(Box::new(suggest));
+        self
+    }
+
+    /// Sets the number of the visible suggestions, the list scrolls
+    /// to the highlighted one beyond. Default: `5`.
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.max_items = max_items.max(1);
+        self
+    }
+
+    /// Sets the help text which is shown in a box above the prompt on `?`.
+    ///
+    /// `?` is not typed into the input then.
+    pub fn help(mut self, help: impl Display) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+
+    /// Submits the answer without the interaction, e.g. for automation,
+    /// drawing only the submitted prompt. The suggestions are not queried.
+    pub fn preset_answer(mut self, answer: impl Display) -> Self {
+        self.preset = Some(answer.to_string());
+        self
+    }
+
+    /// Resolves the prompt with the [`Autocomplete::on_timeout`] action if no key
+    /// is pressed for the duration, the countdown restarts on every key.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout.duration = Some(timeout);
+        self
+    }
+
+    /// Sets what happens on the [`Autocomplete::timeout`]. Default: [`TimeoutAction::Cancel`].
+    pub fn on_timeout(mut self, action: TimeoutAction) -> Self {
+        self.timeout.action = action;
+        self
+    }
+
+    /// Ignores `Esc`, so the prompt can only be submitted. Default: `false`.
+    pub fn no_cancel(mut self, no_cancel: bool) -> Self {
+        self.no_cancel = no_cancel;
+        self
+    }
+
+    /// Discards the keys typed before the prompt shows up, so they can't
+    /// answer it by accident. Default: `false`, the keys typed ahead are kept.
+    pub fn flush_input(mut self, flush: bool) -> Self {
+        self.flush_input = flush;
+        self
+    }
+
+    /// Starts the prompt interaction.
+    pub fn interact(&mut self) -> io::Result<String> {
+        <Self as PromptInteraction<String>>::interact(self)
+    }
+
+    /// Starts the prompt interaction returning a [`PromptError`](crate::PromptError)
+    /// which tells the cancellation apart from the I/O failures.
+    pub fn interact2(&mut self) -> Result<String, crate::PromptError> {
+        Ok(self.interact()?)
+    }
+
+    /// Starts the prompt interaction on the given terminal instead of stderr.
+    pub fn interact_on(&mut self, term: &Term) -> io::Result<String> {
+        <Self as PromptInteraction<String>>::interact_on(self, &mut term.clone())
+    }
+
+    /// Starts the prompt interaction awaiting the keys from the stream.
+    #[cfg(feature = "async")]
+    pub async fn interact_async<S: crate::KeyStream>(
+        &mut self,
+        keys: &mut S,
+    ) -> io::Result<String> {
+        <Self as PromptInteraction<String>>::interact_async(self, keys).await
+    }
+
+    /// Returns the number of lines the prompt occupies on the terminal of
+    /// the given width, e.g. to reserve the space in a composite UI.
+    pub fn rendered_height(&mut self, width: usize) -> usize {
+        <Self as PromptInteraction<String>>::rendered_height(self, &State::Active, width)
+    }
+
+    /// Starts the prompt interaction drawing it to the given writer.
+    ///
+    /// The keys are read from the terminal, see [`Autocomplete::interact`].
+    pub fn interact_with_writer<W: Write>(&mut self, writer: &mut W) -> io::Result<String> {
+        <Self as PromptInteraction<String>>::interact_with_writer(self, writer)
+    }
+
+    /// Queries the suggestions again if the typed text has changed.
+    fn update_matches(&mut self) {
+        let query = self.input.to_string();
+        if self.query.as_ref() == Some(&query) {
+            return;
+        }
+
+        self.matches = match &self.suggest {
+            Some(suggest) => suggest(&query),
+            None => Vec::new(),
+        };
+        self.query = Some(query);
+        self.cursor = None;
+        self.offset = 0;
+    }
+
+    /// Scrolls the visible window to the highlighted suggestion.
+    fn scroll(&mut self) {
+        let cursor = self.cursor.unwrap_or(0);
+        if cursor < self.offset {
+            self.offset = cursor;
+        } else if cursor >= self.offset + self.max_items {
+            self.offset = cursor + 1 - self.max_items;
+        }
+    }
+}
+
+impl PromptInteraction<String> for Autocomplete {
+    fn cancelable(&self) -> bool {
+        !self.no_cancel
+    }
+
+    fn flush_input(&self) -> bool {
+        self.flush_input
+    }
+
+    fn timeout(&self) -> Timeout {
+        self.timeout
+    }
+
+    fn on_timeout(&mut self) -> State<String> {
+        match &self.stored {
+            Some(answer) => State::Submit(answer.clone()),
+            None => State::Cancel,
+        }
+    }
+
+    fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    fn preset(&mut self) -> Option<State<String>> {
+        let answer = self.preset.clone()?;
+        self.input.replace(&answer);
+        self.cursor = None;
+        Some(self.on(&Event::Key(Key::Enter)))
+    }
+
+    fn init(&mut self) {
+        self.stored = stored_answer(&self.prompt_id);
+        if self.input.is_empty() {
+            if let Some(answer) = &self.stored {
+                self.input.extend(answer);
+            }
+        }
+        self.update_matches();
+    }
+
+    fn input(&mut self) -> Option<&mut StringCursor> {
+        Some(&mut self.input)
+    }
+
+    fn on(&mut self, event: &Event) -> State<String> {
+        let Event::Key(key) = event;
+
+        match key {
+            Key::ArrowDown => {
+                self.cursor = match self.cursor {
+                    None if !self.matches.is_empty() => Some(0),
+                    Some(i) if i + 1 < self.matches.len() => Some(i + 1),
+                    cursor => cursor,
+                };
+            }
+            Key::ArrowUp => {
+                self.cursor = match self.cursor {
+                    Some(0) | None => None,
+                    Some(i) => Some(i - 1),
+                };
+            }
+            Key::Tab => {
+                if let Some(i) = self.cursor {
+                    let suggestion = self.matches[i].clone();
+                    self.input.replace(&suggestion);
+                }
+            }
+            Key::Enter => {
+                if let Some(i) = self.cursor {
+                    let suggestion = self.matches[i].clone();
+                    self.input.replace(&suggestion);
+                }
+                if self.input.is_empty() {
+                    return State::Error("Input required".to_string());
+                }
+                return State::Submit(self.input.to_string());
+            }
+            _ => {}
+        }
+
+        self.update_matches();
+        self.scroll();
+
+        State::Active
+    }
+
+    fn render(&mut self, state: &State<String>) -> String {
+        let theme = THEME.lock().unwrap();
+
+        let line1 = theme.format_header(&state.into(), &self.prompt);
+        let line2 = if self.input.is_empty() {
+            theme.format_placeholder(&state.into(), &self.placeholder)
+        } else {
+            let input = fit_input(state, &self.input, &theme.ellipsis());
+            theme.format_input(&state.into(), &input)
+        };
+        let items = self
+            .matches
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(self.max_items)
+            .map(|(i, suggestion)| {
+                theme.format_autocomplete_item(&state.into(), self.cursor == Some(i), suggestion)
+            })
+            .collect::<String>();
+        let line3 = theme.format_footer(&state.into());
+
+        line1 + &line2 + &items + &line3
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+
+    const CRATES: [&str; 5] = ["serde", "serde_json", "serde_yaml", "smallvec", "syn"];
+
+    fn crates() -> Autocomplete {
+        Autocomplete::new("Crate").suggestions(|query| {
+            CRATES
+                .iter()
+                .filter(|name| name.starts_with(query))
+                .map(|name| name.to_string())
+                .collect()
+        })
+    }
+
+    fn type_text(autocomplete: &mut Autocomplete, text: &str) {
+        for chr in text.chars() {
+            autocomplete.handle_key(Key::Char(chr));
+        }
+    }
+
+    fn frame(autocomplete: &mut Autocomplete) -> Vec<String> {
+        let frame = autocomplete.render(&State::Active);
+        console::strip_ansi_codes(&frame)
+            .lines()
+            .map(|line| line.trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn rendered_height_counts_the_initial_suggestions() {
+        let mut autocomplete = Autocomplete::new("Crate")
+            .suggestions(|_| vec!["serde".into(), "tokio".into(), "rand".into()]);
+        let plain = Autocomplete::new("Crate").rendered_height(80);
+        assert_eq!(autocomplete.rendered_height(80), plain + 3);
+    }
+
+    #[test]
+    fn accepted_timeout_never_submits_the_typed_query() {
+        let mut autocomplete = Autocomplete::new("Crate")
+            .suggestions(|_| vec!["serde".into()])
+            .on_timeout(TimeoutAction::AcceptDefault);
+        autocomplete.init();
+        for chr in "ser".chars() {
+            autocomplete.handle_key(Key::Char(chr));
+        }
+        assert!(matches!(autocomplete.timed_out(), State::Cancel));
+    }
+
+    #[test]
+    fn suggestions_are_queried_again_on_every_typed_key() {
+        let queries = Rc::new(RefCell::new(Vec::new()));
+        let log = queries.clone();
+        let mut autocomplete = Autocomplete::new("Crate").suggestions(move |query| {
+            log.borrow_mut().push(query.to_string());
+            vec![format!("{query}-suggested")]
+        });
+        autocomplete.init();
+
+        type_text(&mut autocomplete, "se");
+        autocomplete.handle_key(Key::ArrowLeft);
+        autocomplete.handle_key(Key::Backspace);
+        assert_eq!(*queries.borrow(), ["", "s", "se", "e"]);
+        assert_eq!(autocomplete.matches, ["e-suggested"]);
+    }
+
+    #[test]
+    fn arrows_move_the_highlight_within_the_suggestions() {
+        let mut autocomplete = crates();
+        autocomplete.init();
+        type_text(&mut autocomplete, "serde");
+        assert_eq!(autocomplete.cursor, None);
+
+        autocomplete.handle_key(Key::ArrowDown);
+        assert_eq!(autocomplete.cursor, Some(0));
+        for _ in 0..5 {
+            autocomplete.handle_key(Key::ArrowDown);
+        }
+        assert_eq!(
+            autocomplete.cursor,
+            Some(2),
+            "the highlight stops at the last one"
+        );
+
+        autocomplete.handle_key(Key::ArrowUp);
+        assert_eq!(autocomplete.cursor, Some(1));
+        autocomplete.handle_key(Key::ArrowUp);
+        autocomplete.handle_key(Key::ArrowUp);
+        assert_eq!(
+            autocomplete.cursor, None,
+            "the highlight returns to the input"
+        );
+
+        autocomplete.handle_key(Key::ArrowDown);
+        type_text(&mut autocomplete, "_");
+        assert_eq!(autocomplete.cursor, None, "typing resets the highlight");
+    }
+
+    #[test]
+    fn enter_submits_the_highlighted_suggestion_or_the_typed_text() {
+        let mut autocomplete = crates();
+        autocomplete.init();
+        type_text(&mut autocomplete, "serde");
+        autocomplete.handle_key(Key::ArrowDown);
+        autocomplete.handle_key(Key::ArrowDown);
+        assert_eq!(
+            autocomplete.handle_key(Key::Enter).submitted().as_deref(),
+            Some("serde_json")
+        );
+
+        let mut autocomplete = crates();
+        autocomplete.init();
+        type_text(&mut autocomplete, "ser");
+        assert_eq!(
+            autocomplete.handle_key(Key::Enter).submitted().as_deref(),
+            Some("ser")
+        );
+
+        let mut autocomplete = crates();
+        autocomplete.init();
+        assert!(matches!(
+            autocomplete.handle_key(Key::Enter),
+            State::Error(err) if err == "Input required"
+        ));
+    }
+
+    #[test]
+    fn tab_completes_the_input_with_the_highlighted_suggestion() {
+        let mut autocomplete = crates();
+        autocomplete.init();
+        type_text(&mut autocomplete, "s");
+
+        autocomplete.handle_key(Key::Tab);
+        assert_eq!(
+            autocomplete.input.to_string(),
+            "s",
+            "nothing is highlighted"
+        );
+
+        autocomplete.handle_key(Key::ArrowDown);
+        autocomplete.handle_key(Key::ArrowDown);
+        autocomplete.handle_key(Key::ArrowDown);
+        autocomplete.handle_key(Key::ArrowDown);
+        assert!(matches!(autocomplete.handle_key(Key::Tab), State::Active));
+        assert_eq!(autocomplete.input.to_string(), "smallvec");
+        assert_eq!(
+            autocomplete.matches,
+            ["smallvec"],
+            "the completion is queried"
+        );
+        assert_eq!(autocomplete.cursor, None);
+
+        type_text(&mut autocomplete, "!");
+        assert_eq!(
+            autocomplete.handle_key(Key::Enter).submitted().as_deref(),
+            Some("smallvec!")
+        );
+    }
+
+    #[test]
+    fn max_items_window_scrolls_to_the_highlighted_suggestion() {
+        let mut autocomplete = crates().max_items(2);
+        autocomplete.init();
+        let visible = |autocomplete: &mut Autocomplete| {
+            frame(autocomplete)
+                .into_iter()
+                .filter_map(|line| {
+                    let name = line.split_whitespace().last()?.to_string();
+                    CRATES.contains(&name.as_str()).then_some(name)
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(visible(&mut autocomplete), ["serde", "serde_json"]);
+
+        for _ in 0..4 {
+            autocomplete.handle_key(Key::ArrowDown);
+        }
+        assert_eq!(autocomplete.cursor, Some(3));
+        assert_eq!(visible(&mut autocomplete), ["serde_yaml", "smallvec"]);
+
+        autocomplete.handle_key(Key::ArrowDown);
+        assert_eq!(visible(&mut autocomplete), ["smallvec", "syn"]);
+
+        for _ in 0..3 {
+            autocomplete.handle_key(Key::ArrowUp);
+        }
+        assert_eq!(autocomplete.cursor, Some(1));
+        assert_eq!(visible(&mut autocomplete), ["serde_json", "serde_yaml"]);
+
+        autocomplete.handle_key(Key::ArrowUp);
+        autocomplete.handle_key(Key::ArrowUp);
+        assert_eq!(autocomplete.cursor, None);
+        assert_eq!(visible(&mut autocomplete), ["serde", "serde_json"]);
+    }
+}
@@ -1,10 +1,16 @@
 use std::fmt::Display;
-use std::io;
+use std::io::{self, Write};
+use std::time::Duration;
 
-use console::Key;
+use console::{Key, Term};
 
 use crate::{
-    prompt::interaction::{Event, PromptInteraction, State},
+    keymap::{keymap_or_global, Action, KeyMap},
+    prompt::{
+        cursor::StringCursor,
+        interaction::{Event, PromptInteraction, State, Timeout, TimeoutAction},
+    },
+    state::stored_answer,
     theme::THEME,
 };
 
@@ -14,11 +20,25 @@ use crate::{
 /// * `Enter` to confirm.
 /// * `Y/y` for immediate "yes" answer.
 /// * `N/n` for immediate "no" answer.
+///
+/// With [`Confirm::require_phrase`], the user has to type the exact phrase
+/// instead of answering yes or no.
 #[derive(Default)]
 pub struct Confirm {
     prompt: String,
     input: bool,
     initial_value: bool,
+    prompt_id: Option<String>,
+    phrase: Option<String>,
+    phrase_input: StringCursor,
+    case_sensitive: bool,
+    no_first: bool,
+    no_cancel: bool,
+    flush_input: bool,
+    timeout: Timeout,
+    help: Option<String>,
+    preset: Option<bool>,
+    keymap: Option<KeyMap>,
 }
 
 impl Confirm {
@@ -26,6 +46,7 @@ impl Confirm {
     pub fn new(prompt: impl Display) -> Self {
         Self {
             prompt: prompt.to_string(),
+            case_sensitive: true,
             ..Default::default()
         }
     }
@@ -36,19 +57,214 @@ impl Confirm {
         self
     }
 
+    /// Renders the "No" option before the "Yes" one. Default: `false`.
+    ///
+    /// The answer semantics and the `Y`/`N` keys stay the same.
+    pub fn no_first(mut self, no_first: bool) -> Self {
+        self.no_first = no_first;
+        self
+    }
+
+    /// Requires the user to type the given phrase to confirm, e.g. `DELETE`.
+    ///
+    /// The prompt returns `true` if the typed text matches the phrase,
+    /// and `false` otherwise.
+    pub fn require_phrase(mut self, phrase: impl Display) -> Self {
+        self.phrase = Some(phrase.to_string());
+        self
+    }
+
+    /// Sets whether the phrase set with [`Confirm::require_phrase`] is
+    /// matched case-sensitively. Default: `true`.
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Sets the identifier of the prompt to look up the initial value
+    /// with the global [`StateProvider`](crate::StateProvider).
+    ///
+    /// The stored answer (`true`/`false`) overrides [`Confirm::initial_value`].
+    pub fn prompt_id(mut self, id: &str) -> Self {
+        self.prompt_id = Some(id.into());
+        self
+    }
+
+    /// Sets the help text which is shown in a box above the prompt on `?`.
+    pub fn help(mut self, help: impl Display) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+
+    /// Submits the answer without the interaction, e.g. for a dry run,
+    /// drawing only the submitted prompt.
+    pub fn preset_answer(mut self, answer: bool) -> Self {
+        self.preset = Some(answer);
+        self
+    }
+
+    /// Resolves the prompt with the [`Confirm::on_timeout`] action if no key
+    /// is pressed for the duration, the countdown restarts on every key.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout.duration = Some(timeout);
+        self
+    }
+
+    /// Sets what happens on the [`Confirm::timeout`]. Default: [`TimeoutAction::Cancel`].
+    pub fn on_timeout(mut self, action: TimeoutAction) -> Self {
+        self.timeout.action = action;
+        self
+    }
+
+    /// Sets the key map of the answer keys, the move and toggle actions
+    /// switch the answer. Default: the global one, see [`set_keymap`](crate::set_keymap).
+    ///
+    /// `Y`/`N` keys and the left and right arrows always answer and switch it.
+    pub fn keymap(mut self, keymap: KeyMap) -> Self {
+        self.keymap = Some(keymap);
+        self
+    }
+
+    /// Ignores `Esc`, so the prompt can only be submitted. Default: `false`.
+    pub fn no_cancel(mut self, no_cancel: bool) -> Self {
+        self.no_cancel = no_cancel;
+        self
+    }
+
+    /// Discards the keys typed before the prompt shows up, so they can't
+    /// answer it by accident. Default: `false`, the keys typed ahead are kept.
+    pub fn flush_input(mut self, flush: bool) -> Self {
+        self.flush_input = flush;
+        self
+    }
+
     /// Starts the prompt interaction.
     pub fn interact(&mut self) -> io::Result<bool> {
-        self.input = self.initial_value;
         <Self as PromptInteraction<bool>>::interact(self)
     }
+
+    /// Starts the prompt interaction returning a [`PromptError`](crate::PromptError)
+    /// which tells the cancellation apart from the I/O failures.
+    pub fn interact2(&mut self) -> Result<bool, crate::PromptError> {
+        Ok(self.interact()?)
+    }
+
+    /// Starts the prompt interaction on the given terminal instead of stderr.
+    pub fn interact_on(&mut self, term: &Term) -> io::Result<bool> {
+        <Self as PromptInteraction<bool>>::interact_on(self, &mut term.clone())
+    }
+
+    /// Starts the prompt interaction awaiting the keys from the stream.
+    #[cfg(feature = "async")]
+    pub async fn interact_async<S: crate::KeyStream>(&mut self, keys: &mut S) -> io::Result<bool> {
+        <Self as PromptInteraction<bool>>::interact_async(self, keys).await
+    }
+
+    /// Returns the number of lines the prompt occupies on the terminal of
+    /// the given width, e.g. to reserve the space in a composite UI.
+    pub fn rendered_height(&mut self, width: usize) -> usize {
+        <Self as PromptInteraction<bool>>::rendered_height(self, &State::Active, width)
+    }
+
+    /// Starts the prompt interaction drawing it to the given writer.
+    ///
+    /// The keys are read from the terminal, see [`Confirm::interact`].
+    pub fn interact_with_writer<W: Write>(&mut self, writer: &mut W) -> io::Result<bool> {
+        <Self as PromptInteraction<bool>>::interact_with_writer(self, writer)
+    }
+}
+
+impl Confirm {
+    /// Checks the typed text against the required phrase.
+    fn phrase_matches(&self, phrase: &str) -> bool {
+        let typed = self.phrase_input.to_string();
+        if self.case_sensitive {
+            typed == phrase
+        } else {
+            typed.to_lowercase() == phrase.to_lowercase()
+        }
+    }
 }
 
 impl PromptInteraction<bool> for Confirm {
+    fn cancelable(&self) -> bool {
+        !self.no_cancel
+    }
+
+    fn flush_input(&self) -> bool {
+        self.flush_input
+    }
+
+    fn timeout(&self) -> Timeout {
+        self.timeout
+    }
+
+    fn on_timeout(&mut self) -> State<bool> {
+        if let Some(phrase) = &self.phrase {
+            self.input = self.phrase_matches(phrase);
+        }
+        State::Submit(self.input)
+    }
+
+    fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    fn init(&mut self) {
+        self.input = stored_answer(&self.prompt_id)
+            .and_then(|answer| answer.parse().ok())
+            .unwrap_or(self.initial_value);
+    }
+
+    fn input(&mut self) -> Option<&mut StringCursor> {
+        self.phrase.as_ref()?;
+        Some(&mut self.phrase_input)
+    }
+
+    fn preset(&mut self) -> Option<State<bool>> {
+        self.input = self.preset?;
+        Some(State::Submit(self.input))
+    }
+
+    fn answer_line(&mut self, line: &str) -> State<bool> {
+        if let Some(phrase) = &self.phrase {
+            self.phrase_input.replace(line);
+            self.input = self.phrase_matches(phrase);
+            return State::Submit(self.input);
+        }
+
+        match line.trim().to_lowercase().as_str() {
+            "" => {}
+            "y" | "yes" => self.input = true,
+            "n" | "no" => self.input = false,
+            _ => return State::Error("Expected yes or no".to_string()),
+        }
+        State::Submit(self.input)
+    }
+
     fn on(&mut self, event: &Event) -> State<bool> {
         let Event::Key(key) = event;
 
+        if let Some(phrase) = &self.phrase {
+            if *key == Key::Enter {
+                self.input = self.phrase_matches(phrase);
+                return State::Submit(self.input);
+            }
+            return State::Active;
+        }
+
+        match keymap_or_global(&self.keymap).action(key) {
+            Some(Action::MoveUp | Action::MoveDown | Action::Toggle) => {
+                self.input = !self.input;
+                return State::Active;
+            }
+            Some(Action::Submit) => return State::Submit(self.input),
+            Some(Action::Cancel) if !self.no_cancel => return State::Cancel,
+            _ => {}
+        }
+
         match key {
-            Key::ArrowDown | Key::ArrowRight | Key::ArrowUp | Key::ArrowLeft => {
+            Key::ArrowRight | Key::ArrowLeft => {
                 self.input = !self.input;
             }
             Key::Char('y') | Key::Char('Y') => {
@@ -59,7 +275,6 @@ impl PromptInteraction<bool> for Confirm {
                 self.input = false;
                 return State::Submit(self.input);
             }
-            Key::Enter => return State::Submit(self.input),
             _ => {}
         }
 
@@ -69,9 +284,133 @@ impl PromptInteraction<bool> for Confirm {
     fn render(&mut self, state: &State<bool>) -> String {
         let theme = THEME.lock().unwrap();
         let line1 = theme.format_header(&state.into(), &self.prompt);
-        let line2 = theme.format_confirm(&state.into(), self.input);
+        let line2 = match &self.phrase {
+            Some(phrase) if self.phrase_input.is_empty() => {
+                let mut placeholder = StringCursor::default();
+                placeholder.extend(phrase);
+                theme.format_placeholder(&state.into(), &placeholder)
+            }
+            Some(_) => theme.format_input(&state.into(), &self.phrase_input),
+            None => theme.format_confirm(&state.into(), self.input, self.no_first),
+        };
         let line3 = theme.format_footer(&state.into());
 
         line1 + &line2 + &line3
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(confirm: &mut Confirm, text: &str) {
+        for chr in text.chars() {
+            confirm.handle_key(Key::Char(chr));
+        }
+    }
+
+    #[test]
+    fn matching_phrase_confirms() {
+        let mut confirm = Confirm::new("Delete?").require_phrase("DELETE");
+        confirm.init();
+        type_text(&mut confirm, "DELETE");
+        assert_eq!(confirm.handle_key(Key::Enter).submitted(), Some(true));
+    }
+
+    #[test]
+    fn mismatching_phrase_declines() {
+        let mut confirm = Confirm::new("Delete?").require_phrase("DELETE");
+        confirm.init();
+        type_text(&mut confirm, "delete");
+        assert_eq!(confirm.handle_key(Key::Enter).submitted(), Some(false));
+
+        let mut confirm = Confirm::new("Delete?")
+            .require_phrase("DELETE")
+            .case_sensitive(false);
+        confirm.init();
+        type_text(&mut confirm, "delete");
+        assert_eq!(confirm.handle_key(Key::Enter).submitted(), Some(true));
+    }
+
+    #[test]
+    fn phrase_keys_are_typed_not_answered() {
+        let mut confirm = Confirm::new("Delete?").require_phrase("yes");
+        confirm.init();
+        assert!(matches!(confirm.handle_key(Key::Char('y')), State::Active));
+        type_text(&mut confirm, "es");
+        assert_eq!(confirm.handle_key(Key::Enter).submitted(), Some(true));
+    }
+
+    #[test]
+    fn phrase_prompt_is_cancelled_with_escape() {
+        let mut confirm = Confirm::new("Delete?").require_phrase("DELETE");
+        confirm.init();
+        type_text(&mut confirm, "DEL");
+        assert!(matches!(confirm.handle_key(Key::Escape), State::Cancel));
+    }
+
+    #[test]
+    fn no_first_renders_no_before_yes() {
+        let option_line = |no_first: bool| {
+            let mut confirm = Confirm::new("Continue?").no_first(no_first);
+            confirm.init();
+            let frame = confirm.render(&State::Active);
+            let line = console::strip_ansi_codes(&frame)
+                .lines()
+                .nth(1)
+                .unwrap()
+                .to_string();
+            (line.find("Yes").unwrap(), line.find("No").unwrap())
+        };
+
+        let (yes, no) = option_line(false);
+        assert!(yes < no);
+        let (yes, no) = option_line(true);
+        assert!(no < yes);
+    }
+
+    #[test]
+    fn no_first_keeps_the_keys_and_the_answers() {
+        for (key, answer) in [('y', true), ('n', false)] {
+            let mut confirm = Confirm::new("Continue?").no_first(true);
+            confirm.init();
+            let state = confirm.handle_key(Key::Char(key));
+            assert_eq!(state.submitted(), Some(answer));
+        }
+    }
+
+    #[test]
+    fn space_toggles_the_answer_without_submitting() {
+        let mut confirm = Confirm::new("Continue?").initial_value(true);
+        confirm.init();
+
+        assert!(matches!(confirm.handle_key(Key::Char(' ')), State::Active));
+        assert!(!confirm.input);
+        assert!(matches!(confirm.handle_key(Key::Char(' ')), State::Active));
+        assert!(confirm.input);
+        confirm.handle_key(Key::Char(' '));
+        assert_eq!(confirm.handle_key(Key::Enter).submitted(), Some(false));
+    }
+
+    #[test]
+    fn ctrl_c_cancels_regardless_of_no_cancel() {
+        let mut confirm = Confirm::new("Continue?").no_cancel(true);
+        confirm.init();
+        assert!(matches!(confirm.handle_key(Key::Escape), State::Active));
+        assert!(matches!(confirm.handle_key(Key::CtrlC), State::Cancel));
+    }
+
+    #[test]
+    fn stdin_line_answers_yes_or_no() {
+        let answer = |line: &str| {
+            let mut confirm = Confirm::new("Continue?").initial_value(true);
+            confirm.init();
+            confirm.answer_line(line)
+        };
+
+        assert_eq!(answer("n").submitted(), Some(false));
+        assert_eq!(answer("YES").submitted(), Some(true));
+        assert_eq!(answer("").submitted(), Some(true));
+        assert!(matches!(answer("maybe"), State::Error(_)));
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt::{self, Display, Formatter};
+use std::io;
+
+/// The error of a prompt interaction distinguishing the user's cancellation
+/// and the rejected answers from the terminal I/O failures.
+///
+/// It's returned by the `interact2` methods, e.g. [`Input::interact2`](crate::Input::interact2).
+///
+/// ```
+/// use cliclack::{input, PromptError};
+///
+/// match input("Name").interact2::<String>() {
+///     Ok(name) => println!("Hello, {name}!"),
+///     Err(PromptError::Cancelled) => println!("Maybe next time."),
+///     Err(PromptError::Aborted) => println!("Not a terminal."),
+///     Err(PromptError::Validation(err)) => eprintln!("Invalid answer: {err}"),
+///     Err(err) => eprintln!("Terminal failed: {err}"),
+/// }
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PromptError {
+    /// The terminal I/O failed.
+    Io(io::Error),
+    /// The user cancelled the prompt with `Esc` or `Ctrl+C`.
+    Cancelled,
+    /// The interaction couldn't take place or finish: the output is not
+    /// a terminal, or the stream of keys has ended.
+    Aborted,
+    /// The answer given without the interaction was rejected, e.g. a preset
+    /// answer failing the validation or not being an option.
+    Validation(String),
+}
+
+impl From<io::Error> for PromptError {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::Interrupted => PromptError::Cancelled,
+            io::ErrorKind::NotConnected | io::ErrorKind::UnexpectedEof => PromptError::Aborted,
+            io::ErrorKind::InvalidInput => PromptError::Validation(err.to_string()),
+            _ => PromptError::Io(err),
+        }
+    }
+}
+
+impl Display for PromptError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PromptError::Io(err) => write!(f, "{err}"),
+            PromptError::Cancelled => write!(f, "prompt cancelled"),
+            PromptError::Aborted => write!(f, "prompt aborted"),
+            PromptError::Validation(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for PromptError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PromptError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn io_errors_map_to_the_variants() {
+        let cancelled = PromptError::from(io::Error::from(io::ErrorKind::Interrupted));
+        let aborted = PromptError::from(io::Error::from(io::ErrorKind::NotConnected));
+        let ended = PromptError::from(io::Error::from(io::ErrorKind::UnexpectedEof));
+        let failed = PromptError::from(io::Error::other("broken pipe"));
+        let invalid = PromptError::from(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Input required",
+        ));
+
+        assert!(matches!(cancelled, PromptError::Cancelled));
+        assert!(matches!(aborted, PromptError::Aborted));
+        assert!(matches!(ended, PromptError::Aborted));
+        assert!(matches!(&invalid, PromptError::Validation(err) if err == "Input required"));
+        assert!(matches!(&failed, PromptError::Io(err) if err.to_string() == "broken pipe"));
+        assert!(std::error::Error::source(&failed).is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn interact2_aborts_without_a_terminal() {
+        use crate::{input, output};
+
+        let (result, _) = output::capture(|| input("Name").interact2::<String>());
+        assert!(matches!(result, Err(PromptError::Aborted)));
+
+        let (result, _) =
+            output::capture(|| input("Name").preset_answer("my-app").interact2::<String>());
+        assert_eq!(result.unwrap(), "my-app");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn rejected_preset_is_a_validation_error() {
+        use crate::{output, Number, Select};
+
+        let (result, _) = output::capture(|| {
+            Number::<u8>::new("Level")
+                .min(1)
+                .max(10)
+                .preset_answer(42)
+                .interact2()
+        });
+        assert!(
+            matches!(result, Err(PromptError::Validation(_))),
+            "{result:?}"
+        );
+
+        let (result, _) = output::capture(|| {
+            Select::new("Pick")
+                .item("a", "Alpha", "")
+                .preset_answer("b")
+                .interact2()
+        });
+        assert!(
+            matches!(result, Err(PromptError::Validation(_))),
+            "{result:?}"
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use std::any::Any;
+use std::fmt::Display;
+use std::io;
+
+type Step<'a> = Box<dyn FnOnce(&Answers) -> io::Result<Box<dyn Any>> + 'a>;
+
+/// The submitted values of the [`Group`] steps, looked up by the step names
+/// as the types the steps return.
+#[derive(Default)]
+pub struct Answers {
+    answers: Vec<(String, Box<dyn Any>)>,
+}
+
+impl Answers {
+    /// Returns the value of the given step if it has been run and returned
+    /// the type `T`.
+    pub fn answer<T: 'static>(&self, name: &str) -> Option<&T> {
+        self.answers
+            .iter()
+            .find(|(recorded, _)| recorded == name)
+            .and_then(|(_, value)| value.downcast_ref())
+    }
+
+    /// Takes the value of the given step out of the answers if it has been
+    /// run and returned the type `T`.
+    pub fn take<T: 'static>(&mut self, name: &str) -> Option<T> {
+        let i = self
+            .answers
+            .iter()
+            .position(|(recorded, value)| recorded == name && value.is::<T>())?;
+        let (_, value) = self.answers.remove(i);
+        value.downcast().ok().map(|value| *value)
+    }
+}
+
+/// A sequence of named prompts run under a single intro, which is aborted
+/// as a whole if any of them is cancelled.
+///
+/// The submitted values are collected into [`Answers`] by the step names,
+/// each step sees the answers of the previous ones.
+///
+/// ```
+/// use cliclack::{confirm, group, input};
+///
+/// # fn test() -> std::io::Result<()> {
+/// let answers = group("create-app")
+///     .step("name", |_| input("Project name").interact::<String>())
+///     .step("git", |_| confirm("Initialize git?").interact())
+///     .step("remote", |answers| match answers.answer::<bool>("git") {
+///         Some(true) => input("Remote URL").interact::<String>(),
+///         _ => Ok(String::new()),
+///     })
+///     .outro("You're all set!")
+///     .run()?;
+///
+/// let name: Option<&String> = answers.answer("name");
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
+pub struct Group<'a> {
+    title: String,
+    steps: Vec<(String, Step<'a>)>,
+    outro: Option<String>,
+    cancel_message: String,
+}
+
+impl<'a> Group<'a> {
+    /// Creates a new group with the title of the intro.
+    pub fn new(title: impl Display) -> Self {
+        Self {
+            title: title.to_string(),
+            steps: vec![],
+            outro: None,
+            cancel_message: "Operation cancelled.".to_string(),
+        }
+    }
+
+    /// Adds a step named to look its value up in the answers, the closure
+    /// runs the prompt getting the answers of the previous steps.
+    pub fn step<T, F>(mut self, name: impl Display, step: F) -> Self
+    where
+        T: 'static,
+        F: FnOnce(&Answers) -> io::Result<T> + 'a,
+    {
+        let step =
+            move |answers: &Answers| step(answers).map(|value| Box::new(value) as Box<dyn Any>);
+        self.steps.push((name.to_string(), Box::new(step)));
+        self
+    }
+
+    /// Sets the outro message printed after the last step. Default: none,
+    /// so the frame is continued by the following output.
+    pub fn outro(mut self, message: impl Display) -> Self {
+        self.outro = Some(message.to_string());
+        self
+    }
+
+    /// Sets the outro message printed if a step is cancelled.
+    /// Default: `Operation cancelled.`
+    pub fn cancel_message(mut self, message: impl Display) -> Self {
+        self.cancel_message = message.to_string();
+        self
+    }
+
+    /// Runs the steps in order, returning the collected answers.
+    ///
+    /// A cancelled step ends the group with the cancel outro and
+    /// [`io::ErrorKind::Interrupted`] error, the other errors are returned
+    /// as they are.
+    pub fn run(self) -> io::Result<Answers> {
+        crate::intro(&self.title)?;
+
+        let mut answers = Answers::default();
+        for (name, step) in self.steps {
+            match step(&answers) {
+                Ok(value) => answers.answers.push((name, value)),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {
+                    crate::outro_cancel(&self.cancel_message)?;
+                    return Err(err);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        if let Some(outro) = &self.outro {
+            crate::outro(outro)?;
+        }
+        Ok(answers)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::{confirm, input, output};
+
+    #[test]
+    fn steps_branch_on_the_typed_previous_answers() {
+        let run = |database: bool| {
+            output::capture(|| {
+                Group::new("create-app")
+                    .step("database", move |_| {
+                        confirm("Use a database?")
+                            .preset_answer(database)
+                            .interact()
+                    })
+                    .step("url", |answers| match answers.answer::<bool>("database") {
+                        Some(true) => input("Database URL")
+                            .preset_answer("postgres://")
+                            .interact::<String>()
+                            .map(Some),
+                        _ => Ok(None),
+                    })
+                    .run()
+                    .unwrap()
+            })
+        };
+
+        let (mut answers, text) = run(true);
+        assert_eq!(answers.answer::<bool>("database"), Some(&true));
+        assert_eq!(answers.answer::<String>("database"), None, "another type");
+        let url = answers.take::<Option<String>>("url");
+        assert_eq!(url, Some(Some("postgres://".to_string())));
+        assert!(text.contains("Database URL"));
+
+        let (answers, text) = run(false);
+        assert_eq!(answers.answer::<Option<String>>("url"), Some(&None));
+        assert!(!text.contains("Database URL"));
+    }
+
+    #[test]
+    fn cancelled_step_ends_the_group_with_the_cancel_outro() {
+        let (result, text) = output::capture(|| {
+            Group::new("create-app")
+                .step("name", |_| -> io::Result<String> {
+                    Err(io::ErrorKind::Interrupted.into())
+                })
+                .step("git", |_| {
+                    confirm("Initialize git?").preset_answer(true).interact()
+                })
+                .outro("You're all set!")
+                .run()
+        });
+
+        let err = result.err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+        assert!(text.contains("Operation cancelled."), "{text}");
+        assert!(!text.contains("Initialize git?"));
+        assert!(!text.contains("You're all set!"));
+    }
+}
@@ -1,18 +1,32 @@
-use std::io;
+use std::io::{self, Write};
+use std::time::Duration;
 use std::{fmt::Display, str::FromStr};
 
-use console::Key;
+use console::{Key, Term};
 
 use crate::{
     prompt::{
         cursor::StringCursor,
-        interaction::{Event, PromptInteraction, State},
+        interaction::{
+            fit_input, redraw_in_place, Event, PromptInteraction, State, Timeout, TimeoutAction,
+        },
     },
+    state::stored_answer,
     theme::THEME,
     validate::Validate,
 };
 
 type ValidationCallback = Box<dyn Fn(&String) -> Result<(), String>>;
+type CharFilter = Box<dyn Fn(char) -> bool>;
+
+/// Checks whether the text is a number being typed, like `-3.`: an optional
+/// leading minus, digits, and at most one decimal point.
+pub(crate) fn is_number_prefix(text: &str) -> bool {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+    let mut parts = digits.splitn(2, '.');
+
+    parts.all(|part| part.chars().all(|chr| chr.is_ascii_digit()))
+}
 
 /// A prompt that accepts a single line of text input.
 ///
@@ -36,7 +50,27 @@ pub struct Input {
     input_required: bool,
     default: Option<String>,
     placeholder: StringCursor,
+    placeholder_persist: bool,
+    typed: bool,
     validate: Option<ValidationCallback>,
+    warn: Option<ValidationCallback>,
+    warning: Option<String>,
+    prompt_id: Option<String>,
+    select_all_on_focus: bool,
+    restrict_digits: bool,
+    allow_char: Option<CharFilter>,
+    no_cancel: bool,
+    flush_input: bool,
+    timeout: Timeout,
+    validate_on_start: bool,
+    inline_error: bool,
+    submit_key: Option<Key>,
+    status: Option<bool>,
+    help: Option<String>,
+    preset: Option<String>,
+    history: Vec<String>,
+    history_pos: Option<usize>,
+    draft: String,
 }
 
 impl Input {
@@ -49,29 +83,101 @@ impl Input {
         }
     }
 
-    /// Sets the placeholder (hint) text for the input.
+    /// Sets the placeholder (hint) text shown while the input is empty,
+    /// see [`Input::placeholder_persist`].
     pub fn placeholder(mut self, placeholder: &str) -> Self {
         self.placeholder.extend(placeholder);
         self
     }
 
-    /// Sets the default value for the input and also a hint (placeholder) if one is not already set.
+    /// Sets whether the placeholder reappears when the typed text is erased.
+    /// Default: `false`, it's shown only until the typing starts.
+    pub fn placeholder_persist(mut self, persist: bool) -> Self {
+        self.placeholder_persist = persist;
+        self
+    }
+
+    /// Sets the value submitted when `Enter` is pressed on the empty input.
     ///
-    /// [`Input::placeholder`] overrides a hint set by `default()`, however, default value
-    /// is used is no value has been supplied.
-    pub fn default_input(mut self, value: &str) -> Self {
-        self.default = Some(value.into());
+    /// It's shown dimmed while nothing is typed unless [`Input::placeholder`]
+    /// is set, the validation runs against it as if it were typed.
+    pub fn default_value(mut self, value: impl Display) -> Self {
+        self.default = Some(value.to_string());
         self
     }
 
+    /// Sets the default value for the input, see [`Input::default_value`].
+    pub fn default_input(self, value: &str) -> Self {
+        self.default_value(value)
+    }
+
     /// Sets whether the input is required. Default: `true`.
     ///
-    /// [`Input::default_input`] is used if no value is supplied.
+    /// [`Input::default_value`] is used if no value is supplied.
     pub fn required(mut self, required: bool) -> Self {
         self.input_required = required;
         self
     }
 
+    /// Prefills the input with the default value selected as a whole, so the
+    /// first typed character replaces it. Default: `false`.
+    ///
+    /// Arrow keys deselect the value keeping it for editing.
+    pub fn select_all_on_focus(mut self, select_all: bool) -> Self {
+        self.select_all_on_focus = select_all;
+        self
+    }
+
+    /// Restricts typing to a number: digits, a leading `-` and a single
+    /// decimal point. Default: `false`.
+    pub fn restrict_digits(mut self, restrict: bool) -> Self {
+        self.restrict_digits = restrict;
+        self
+    }
+
+    /// Restricts typing to the characters matching the predicate, e.g.
+    /// `char::is_alphanumeric` for identifiers, the others are ignored.
+    ///
+    /// A pasted text is filtered the same way, character by character.
+    pub fn allow_char<F>(mut self, allow: F) -> Self
+    where
+        F: Fn(char) -> bool + 'static,
+    {
+        self.allow_char = Some(Box::new(allow));
+        self
+    }
+
+    /// Sets the previously entered values, the oldest first, which `Up` and
+    /// `Down` arrows recall like in a shell.
+    ///
+    /// Editing a recalled value doesn't change the history, and the text
+    /// typed before browsing is restored below the most recent entry.
+    pub fn history(mut self, history: Vec<String>) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Replaces the input with the history entry, `None` restores the draft.
+    fn recall(&mut self, pos: Option<usize>) {
+        if self.history_pos.is_none() {
+            self.draft = self.input.to_string();
+        }
+        self.history_pos = pos;
+        match pos {
+            Some(pos) => self.input.replace(&self.history[pos]),
+            None => self.input.replace(&std::mem::take(&mut self.draft)),
+        }
+    }
+
+    /// Sets the identifier of the prompt to look up a default value
+    /// with the global [`StateProvider`](crate::StateProvider).
+    ///
+    /// The stored answer is used only if [`Input::default_value`] is not set.
+    pub fn prompt_id(mut self, id: &str) -> Self {
+        self.prompt_id = Some(id.into());
+        self
+    }
+
     /// Sets a validation callback for the input.
     pub fn validate<V>(mut self, validator: V) -> Self
     where
@@ -84,18 +190,212 @@ impl Input {
         self
     }
 
+    /// Sets a validation callback which only warns, e.g. about a suspicious
+    /// but valid value: the warning is shown on submit, and submitting
+    /// the value again proceeds.
+    ///
+    /// It runs after [`Input::validate`], and the answers given without
+    /// the interaction (see [`Input::preset_answer`]) are not warned about.
+    pub fn validate_warning<V>(mut self, validator: V) -> Self
+    where
+        V: Validate<String> + 'static,
+        V::Err: ToString,
+    {
+        self.warn = Some(Box::new(move |input: &String| {
+            validator.validate(input).map_err(|err| err.to_string())
+        }));
+        self
+    }
+
+    /// Sets the key submitting the prompt instead of `Enter`, e.g. `Key::Tab`.
+    /// `Enter` is ignored then.
+    pub fn submit_key(mut self, key: Key) -> Self {
+        self.submit_key = Some(key);
+        self
+    }
+
+    /// Sets the help text which is shown in a box above the prompt on `?`.
+    ///
+    /// `?` is not typed into the input then.
+    pub fn help(mut self, help: impl Display) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+
+    /// Submits the answer without the interaction, e.g. for a dry run or
+    /// automation, drawing only the submitted prompt.
+    ///
+    /// The answer is validated as if it were typed, a failure is returned as
+    /// [`io::ErrorKind::InvalidInput`] error.
+    ///
+    /// ```
+    /// use cliclack::Input;
+    ///
+    /// let port: u16 = Input::new("Port").preset_answer("8080").interact().unwrap();
+    /// assert_eq!(port, 8080);
+    ///
+    /// let result: std::io::Result<u16> = Input::new("Port")
+    ///     .validate(|input: &String| match input.parse::<u16>() {
+    ///         Ok(port) if port >= 1024 => Ok(()),
+    ///         _ => Err("Unprivileged port expected"),
+    ///     })
+    ///     .preset_answer("80")
+    ///     .interact();
+    /// assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    /// ```
+    pub fn preset_answer(mut self, answer: impl Display) -> Self {
+        self.preset = Some(answer.to_string());
+        self
+    }
+
+    /// Resolves the prompt with the [`Input::on_timeout`] action if no key
+    /// is pressed for the duration, the countdown restarts on every key.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout.duration = Some(timeout);
+        self
+    }
+
+    /// Sets what happens on the [`Input::timeout`]. Default: [`TimeoutAction::Cancel`].
+    pub fn on_timeout(mut self, action: TimeoutAction) -> Self {
+        self.timeout.action = action;
+        self
+    }
+
+    /// Ignores `Esc`, so the prompt can only be submitted. Default: `false`.
+    pub fn no_cancel(mut self, no_cancel: bool) -> Self {
+        self.no_cancel = no_cancel;
+        self
+    }
+
+    /// Discards the keys typed before the prompt shows up, so they can't
+    /// answer it by accident. Default: `false`, the keys typed ahead are kept.
+    pub fn flush_input(mut self, flush: bool) -> Self {
+        self.flush_input = flush;
+        self
+    }
+
+    /// Validates the default value when the prompt starts, so the error shows
+    /// up on the first rendering rather than after `Enter`. Default: `false`.
+    pub fn validate_on_start(mut self, validate: bool) -> Self {
+        self.validate_on_start = validate;
+        self
+    }
+
+    /// Renders the validation error next to the input instead of the footer,
+    /// so the frame keeps its lines during the validation. Default: `false`.
+    pub fn inline_error(mut self, inline: bool) -> Self {
+        self.inline_error = inline;
+        self
+    }
+
     /// Starts the prompt interaction.
     pub fn interact<T>(&mut self) -> io::Result<T>
     where
         T: FromStr,
     {
-        if self.placeholder.is_empty() {
+        <Self as PromptInteraction<T>>::interact(self)
+    }
+
+    /// Starts the prompt interaction returning a [`PromptError`](crate::PromptError)
+    /// which tells the cancellation apart from the I/O failures.
+    pub fn interact2<T>(&mut self) -> Result<T, crate::PromptError>
+    where
+        T: FromStr,
+    {
+        Ok(self.interact()?)
+    }
+
+    /// Starts the prompt interaction on the given terminal instead of stderr.
+    pub fn interact_on<T>(&mut self, term: &Term) -> io::Result<T>
+    where
+        T: FromStr,
+    {
+        <Self as PromptInteraction<T>>::interact_on(self, &mut term.clone())
+    }
+
+    /// Starts the prompt interaction awaiting the keys from the stream.
+    #[cfg(feature = "async")]
+    pub async fn interact_async<T, S>(&mut self, keys: &mut S) -> io::Result<T>
+    where
+        T: FromStr,
+        S: crate::KeyStream,
+    {
+        <Self as PromptInteraction<T>>::interact_async(self, keys).await
+    }
+
+    /// Redraws the submitted prompt appending a status icon (like `✔` or `✖`)
+    /// to the value, e.g. once the value has been checked afterwards.
+    ///
+    /// Nothing must be printed to the output since the submission.
+    pub fn finalize(&mut self, success: bool) -> io::Result<()> {
+        self.finalize_with_writer(&mut crate::output::term(), success)
+    }
+
+    /// Redraws the submitted prompt with a status icon like [`Input::finalize`]
+    /// on the writer the prompt was drawn to, see [`Input::interact_with_writer`].
+    pub fn finalize_with_writer<W: Write>(
+        &mut self,
+        writer: &mut W,
+        success: bool,
+    ) -> io::Result<()> {
+        let submitted = State::Submit(String::new());
+        let prev_frame = <Self as PromptInteraction<String>>::render(self, &submitted);
+        self.status = Some(success);
+        let frame = <Self as PromptInteraction<String>>::render(self, &submitted);
+
+        redraw_in_place(writer, prev_frame, frame)
+    }
+
+    /// Returns the number of lines the prompt occupies on the terminal of
+    /// the given width, e.g. to reserve the space in a composite UI.
+    pub fn rendered_height(&mut self, width: usize) -> usize {
+        <Self as PromptInteraction<String>>::rendered_height(self, &State::Active, width)
+    }
+
+    /// Starts the prompt interaction drawing it to the given writer.
+    ///
+    /// The keys are read from the terminal, see [`Input::interact`].
+    pub fn interact_with_writer<T, W>(&mut self, writer: &mut W) -> io::Result<T>
+    where
+        T: FromStr,
+        W: Write,
+    {
+        <Self as PromptInteraction<T>>::interact_with_writer(self, writer)
+    }
+}
+
+impl Input {
+    /// Submits the typed or the default value checking it, the warning of
+    /// [`Input::validate_warning`] holds the first submit of the value
+    /// if `warn` is set.
+    fn submit<T: FromStr>(&mut self, warn: bool) -> State<T> {
+        if self.input.is_empty() {
             if let Some(default) = &self.default {
-                self.placeholder.extend(default);
-                self.placeholder.extend(" (default)");
+                self.input.extend(default);
+            } else if self.input_required {
+                return State::Error("Input required".to_string());
             }
         }
-        <Self as PromptInteraction<T>>::interact(self)
+
+        let value = self.input.to_string();
+        if let Some(validator) = &self.validate {
+            if let Err(err) = validator(&value) {
+                return State::Error(err);
+            }
+        }
+        if let (Some(validator), true) = (&self.warn, warn) {
+            if let Err(warning) = validator(&value) {
+                if self.warning.as_ref() != Some(&warning) {
+                    self.warning = Some(warning);
+                    return State::Active;
+                }
+            }
+        }
+
+        match value.parse::<T>() {
+            Ok(value) => State::Submit(value),
+            Err(_) => State::Error("Invalid value format".to_string()),
+        }
     }
 }
 
@@ -103,36 +403,104 @@ impl<T> PromptInteraction<T> for Input
 where
     T: FromStr,
 {
+    fn cancelable(&self) -> bool {
+        !self.no_cancel
+    }
+
+    fn flush_input(&self) -> bool {
+        self.flush_input
+    }
+
+    fn timeout(&self) -> Timeout {
+        self.timeout
+    }
+
+    fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    fn init(&mut self) {
+        if self.default.is_none() {
+            self.default = stored_answer(&self.prompt_id);
+        }
+        if self.select_all_on_focus && self.input.is_empty() {
+            if let Some(default) = &self.default {
+                self.input.extend(default);
+                self.input.select_all();
+            }
+        }
+    }
+
+    fn initial_state(&mut self) -> State<T> {
+        if !self.validate_on_start {
+            return State::Active;
+        }
+
+        let value = match &self.default {
+            Some(default) if self.input.is_empty() => default.clone(),
+            _ => self.input.to_string(),
+        };
+
+        match &self.validate {
+            Some(validator) => match validator(&value) {
+                Ok(()) => State::Active,
+                Err(err) => State::Error(err),
+            },
+            None => State::Active,
+        }
+    }
+
+    fn preset(&mut self) -> Option<State<T>> {
+        let answer = self.preset.clone()?;
+        Some(self.answer_line(&answer))
+    }
+
+    fn answer_line(&mut self, line: &str) -> State<T> {
+        self.input.replace(line);
+        <Self as PromptInteraction<T>>::on_timeout(self)
+    }
+
+    fn on_timeout(&mut self) -> State<T> {
+        self.submit(false)
+    }
+
     fn input(&mut self) -> Option<&mut StringCursor> {
         Some(&mut self.input)
     }
 
+    fn accept_char(&self, chr: char) -> bool {
+        if let Some(allow) = &self.allow_char {
+            if !allow(chr) {
+                return false;
+            }
+        }
+        !self.restrict_digits || is_number_prefix(&self.input.with_inserted(chr))
+    }
+
     fn on(&mut self, event: &Event) -> State<T> {
         let Event::Key(key) = event;
 
-        if *key == Key::Enter {
-            if self.input.is_empty() {
-                if let Some(default) = &self.default {
-                    self.input.extend(default);
-                } else if self.input_required {
-                    return State::Error("Input required".to_string());
-                }
-            }
+        if !self.input.is_empty() {
+            self.typed = true;
+        }
 
-            if let Some(validator) = &self.validate {
-                if let Err(err) = validator(&self.input.to_string()) {
-                    return State::Error(err);
-                }
+        match (key, self.history_pos) {
+            (Key::ArrowUp, None) if !self.history.is_empty() => {
+                self.recall(Some(self.history.len() - 1));
             }
-
-            match self.input.to_string().parse::<T>() {
-                Ok(value) => return State::Submit(value),
-                Err(_) => {
-                    return State::Error("Invalid value format".to_string());
-                }
+            (Key::ArrowUp, Some(pos)) if pos > 0 => self.recall(Some(pos - 1)),
+            (Key::ArrowDown, Some(pos)) if pos + 1 < self.history.len() => {
+                self.recall(Some(pos + 1));
             }
+            (Key::ArrowDown, Some(_)) => self.recall(None),
+            _ => {}
         }
 
+        if key == self.submit_key.as_ref().unwrap_or(&Key::Enter) {
+            return self.submit(true);
+        }
+        self.warning = None;
+
         State::Active
     }
 
@@ -140,13 +508,416 @@ where
         let theme = THEME.lock().unwrap();
 
         let line1 = theme.format_header(&state.into(), &self.prompt);
-        let line2 = if self.input.is_empty() {
-            theme.format_placeholder(&state.into(), &self.placeholder)
-        } else {
-            theme.format_input(&state.into(), &self.input)
+        let line2 = match &self.default {
+            _ if !self.input.is_empty() => {
+                let input = fit_input(state, &self.input, &theme.ellipsis());
+                theme.format_input(&state.into(), &input)
+            }
+            Some(default) if self.placeholder.is_empty() => {
+                let mut cursor = StringCursor::default();
+                cursor.extend(default);
+                theme.format_default_value(&state.into(), &cursor)
+            }
+            _ if self.typed && !self.placeholder_persist => {
+                theme.format_input(&state.into(), &self.input)
+            }
+            _ => theme.format_placeholder(&state.into(), &self.placeholder),
+        };
+        let line2 = match (state, self.status) {
+            (State::Submit(_), Some(success)) => format!(
+                "{} {}\n",
+                line2.trim_end_matches('\n'),
+                theme.format_status(success)
+            ),
+            (State::Error(err), _) if self.inline_error => format!(
+                "{}{}\n",
+                line2.trim_end_matches('\n'),
+                theme.format_inline_error(err)
+            ),
+            _ => line2,
+        };
+        let line3 = match (state, &self.warning) {
+            (State::Error(err), _) if self.inline_error => theme.format_footer_inline_error(err),
+            (State::Active, Some(warning)) => theme.format_footer_warning(warning),
+            _ => theme.format_footer(&state.into()),
         };
-        let line3 = theme.format_footer(&state.into());
 
         line1 + &line2 + &line3
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prompt::interaction::render_width;
+
+    fn init(input: &mut Input) {
+        PromptInteraction::<String>::init(input);
+    }
+
+    fn press(input: &mut Input, key: Key) -> State<String> {
+        PromptInteraction::<String>::handle_key(input, key)
+    }
+
+    fn type_text(input: &mut Input, text: &str) {
+        for chr in text.chars() {
+            press(input, Key::Char(chr));
+        }
+    }
+
+    #[test]
+    fn select_all_on_focus_replaces_the_default_on_typing() {
+        let mut input = Input::new("Name")
+            .default_value("my-app")
+            .select_all_on_focus(true);
+        init(&mut input);
+
+        type_text(&mut input, "web");
+        assert_eq!(
+            press(&mut input, Key::Enter).submitted().as_deref(),
+            Some("web")
+        );
+    }
+
+    #[test]
+    fn select_all_on_focus_keeps_the_default_on_arrows() {
+        let mut input = Input::new("Name")
+            .default_value("app")
+            .select_all_on_focus(true);
+        init(&mut input);
+
+        press(&mut input, Key::ArrowRight);
+        type_text(&mut input, "-web");
+        assert_eq!(
+            press(&mut input, Key::Enter).submitted().as_deref(),
+            Some("app-web")
+        );
+    }
+
+    #[test]
+    fn warning_holds_the_first_submit() {
+        let mut input =
+            Input::new("Port").validate_warning(|value: &String| match value.as_str() {
+                "80" => Err("Needs root privileges"),
+                _ => Ok(()),
+            });
+        init(&mut input);
+
+        type_text(&mut input, "80");
+        assert!(matches!(press(&mut input, Key::Enter), State::Active));
+        let frame = PromptInteraction::<String>::render(&mut input, &State::Active);
+        assert!(console::strip_ansi_codes(&frame).contains("Needs root privileges"));
+
+        assert_eq!(
+            press(&mut input, Key::Enter).submitted().as_deref(),
+            Some("80")
+        );
+    }
+
+    #[test]
+    fn warning_is_shown_again_after_editing() {
+        let mut input = Input::new("Port").validate_warning(|value: &String| match value.len() {
+            1 | 2 => Err("Needs root privileges"),
+            _ => Ok(()),
+        });
+        init(&mut input);
+
+        type_text(&mut input, "80");
+        press(&mut input, Key::Enter);
+        press(&mut input, Key::Backspace);
+        let frame = PromptInteraction::<String>::render(&mut input, &State::Active);
+        assert!(!console::strip_ansi_codes(&frame).contains("Needs root privileges"));
+
+        assert!(matches!(press(&mut input, Key::Enter), State::Active));
+        assert_eq!(
+            press(&mut input, Key::Enter).submitted().as_deref(),
+            Some("8")
+        );
+    }
+
+    #[test]
+    fn warning_doesnt_hold_the_preset_answer() {
+        let mut input = Input::new("Port")
+            .validate_warning(|_: &String| Err("Needs root privileges"))
+            .preset_answer("80");
+        let state = PromptInteraction::<String>::preset(&mut input).unwrap();
+        assert_eq!(state.submitted().as_deref(), Some("80"));
+    }
+
+    #[test]
+    fn long_input_is_truncated_with_the_theme_ellipsis() {
+        struct Dots;
+        impl crate::Theme for Dots {
+            fn ellipsis(&self) -> String {
+                "...".into()
+            }
+        }
+
+        let mut input = Input::new("Name");
+        init(&mut input);
+        type_text(&mut input, &"x".repeat(500));
+
+        let frame = crate::theme::with_theme(Dots, || {
+            PromptInteraction::<String>::render(&mut input, &State::Active)
+        });
+        let frame = console::strip_ansi_codes(&frame);
+        let line = frame.lines().nth(1).unwrap();
+        assert!(line.contains("  ...xxx"), "{line}");
+        assert!(console::measure_text_width(line) <= render_width());
+    }
+
+    #[test]
+    fn flush_input_is_opt_in() {
+        let flushes = |input: &Input| PromptInteraction::<String>::flush_input(input);
+        assert!(!flushes(&Input::new("Name")));
+        assert!(flushes(&Input::new("Name").flush_input(true)));
+    }
+
+    #[test]
+    fn number_prefixes_allow_one_leading_minus_and_one_point() {
+        for text in ["", "-", "3", "-3.", "-3.14", ".5"] {
+            assert!(is_number_prefix(text), "{text:?}");
+        }
+        for text in ["--", "3-", "1.2.", "-.-", "1e3", " 1"] {
+            assert!(!is_number_prefix(text), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn restricted_digits_reject_the_malformed_numbers() {
+        let typed = |text: &str| {
+            let mut input = Input::new("Offset").restrict_digits(true);
+            init(&mut input);
+            type_text(&mut input, text);
+            input.input.to_string()
+        };
+
+        assert_eq!(typed("-3.14"), "-3.14");
+        assert_eq!(typed("--1"), "-1");
+        assert_eq!(typed("1.2.3"), "1.23");
+        assert_eq!(typed("4-2"), "42");
+        assert_eq!(typed("x7"), "7");
+    }
+
+    #[test]
+    fn no_cancel_ignores_escape_but_not_ctrl_c() {
+        let mut input = Input::new("Name").no_cancel(true);
+        init(&mut input);
+        type_text(&mut input, "app");
+
+        assert!(matches!(press(&mut input, Key::Escape), State::Active));
+        assert_eq!(input.input.to_string(), "app");
+        assert!(matches!(press(&mut input, Key::CtrlC), State::Cancel));
+
+        let mut input = Input::new("Name");
+        init(&mut input);
+        assert!(matches!(press(&mut input, Key::Escape), State::Cancel));
+    }
+
+    #[test]
+    fn invalid_default_is_reported_on_the_first_frame() {
+        let port = |value: &String| match value.parse::<u16>() {
+            Ok(_) => Ok(()),
+            Err(_) => Err("Not a port"),
+        };
+        let mut input = Input::new("Port")
+            .default_input("80000")
+            .validate(port)
+            .validate_on_start(true);
+        init(&mut input);
+
+        let state = PromptInteraction::<String>::initial_state(&mut input);
+        assert!(matches!(&state, State::Error(err) if err == "Not a port"));
+        let frame = PromptInteraction::<String>::render(&mut input, &state);
+        assert!(console::strip_ansi_codes(&frame).contains("Not a port"));
+
+        type_text(&mut input, "8000");
+        assert_eq!(
+            press(&mut input, Key::Enter).submitted().as_deref(),
+            Some("8000")
+        );
+
+        let mut input = Input::new("Port").default_input("80000").validate(port);
+        init(&mut input);
+        let state = PromptInteraction::<String>::initial_state(&mut input);
+        assert!(matches!(state, State::Active), "validated on submit only");
+    }
+
+    #[test]
+    fn rendered_height_counts_the_wrapped_lines() {
+        let mut input = Input::new("Name");
+        assert_eq!(input.rendered_height(80), 3);
+
+        let mut input = Input::new("A rather long prompt title").default_input("default");
+        assert_eq!(input.rendered_height(80), 3);
+        assert_eq!(input.rendered_height(16), 5);
+    }
+
+    #[test]
+    fn submit_key_replaces_enter() {
+        let mut input = Input::new("Name").submit_key(Key::Tab);
+        init(&mut input);
+
+        type_text(&mut input, "my-app");
+        assert!(matches!(press(&mut input, Key::Enter), State::Active));
+        assert_eq!(
+            press(&mut input, Key::Tab).submitted().as_deref(),
+            Some("my-app")
+        );
+    }
+
+    #[test]
+    fn alt_b_alt_f_and_ctrl_w_edit_by_words() {
+        let mut input = Input::new("Command");
+        init(&mut input);
+        type_text(&mut input, "cargo add serde");
+
+        press(&mut input, Key::UnknownEscSeq(vec!['b']));
+        press(&mut input, Key::UnknownEscSeq(vec!['b']));
+        type_text(&mut input, "-q ");
+        press(&mut input, Key::UnknownEscSeq(vec!['f']));
+        press(&mut input, Key::Char('\u{17}'));
+        assert_eq!(
+            press(&mut input, Key::Enter).submitted().as_deref(),
+            Some("cargo -q  serde")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn finalize_redraws_the_submitted_line_with_the_status() {
+        let mut input = Input::new("Name").preset_answer("my-app");
+        let (name, output) = crate::output::capture(|| {
+            let name = input.interact::<String>();
+            input.finalize(true).unwrap();
+            name
+        });
+        assert_eq!(name.unwrap(), "my-app");
+
+        let status = THEME.lock().unwrap().format_status(true);
+        let output = console::strip_ansi_codes(&output);
+        let status = console::strip_ansi_codes(&status);
+        let finalized = output.find(&format!("my-app {status}")).unwrap();
+        assert!(output.find("my-app\n").unwrap() < finalized, "{output:?}");
+    }
+
+    #[test]
+    fn allow_char_drops_the_disallowed_characters() {
+        let mut input =
+            Input::new("Identifier").allow_char(|chr| chr.is_alphanumeric() || chr == '_');
+        init(&mut input);
+
+        type_text(&mut input, "my app-name_1!");
+        assert_eq!(
+            press(&mut input, Key::Enter).submitted().as_deref(),
+            Some("myappname_1")
+        );
+    }
+
+    #[test]
+    fn preset_answer_is_validated_and_drawn_submitted() {
+        let validate = |input: &String| match input.len() {
+            0..=2 => Err("Too short"),
+            _ => Ok(()),
+        };
+
+        let mut out = Vec::new();
+        let name: String = Input::new("Name")
+            .validate(validate)
+            .preset_answer("my-app")
+            .interact_with_writer(&mut out)
+            .unwrap();
+        assert_eq!(name, "my-app");
+        let frame = console::strip_ansi_codes(std::str::from_utf8(&out).unwrap()).into_owned();
+        assert!(frame.contains("Name") && frame.contains("my-app"));
+
+        let mut out = Vec::new();
+        let err = Input::new("Name")
+            .validate(validate)
+            .preset_answer("ab")
+            .interact_with_writer::<String, _>(&mut out)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(err.to_string(), "Too short");
+        assert!(String::from_utf8(out).unwrap().contains("Too short"));
+    }
+
+    #[test]
+    fn placeholder_reappears_on_erasing_only_if_it_persists() {
+        let shown = |input: &mut Input| {
+            let frame = PromptInteraction::<String>::render(input, &State::Active);
+            console::strip_ansi_codes(&frame).contains("my-app")
+        };
+
+        for persist in [false, true] {
+            let mut input = Input::new("Name")
+                .placeholder("my-app")
+                .placeholder_persist(persist);
+            init(&mut input);
+            assert!(shown(&mut input));
+
+            type_text(&mut input, "w");
+            assert!(!shown(&mut input));
+
+            press(&mut input, Key::Backspace);
+            assert_eq!(shown(&mut input), persist);
+        }
+    }
+
+    #[test]
+    fn inline_error_is_rendered_on_the_input_line() {
+        let lines = |inline: bool| {
+            let mut input = Input::new("Age").inline_error(inline);
+            init(&mut input);
+            type_text(&mut input, "abc");
+            let active = PromptInteraction::<String>::render(&mut input, &State::Active);
+            let error = State::Error("Must be a number".into());
+            let frame = PromptInteraction::<String>::render(&mut input, &error);
+            assert_eq!(frame.lines().count(), active.lines().count());
+            console::strip_ansi_codes(&frame)
+                .lines()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        let inline = lines(true);
+        let input_line = inline.iter().find(|line| line.contains("abc")).unwrap();
+        assert!(input_line.ends_with("  Must be a number"), "{inline:?}");
+        assert!(!inline.last().unwrap().contains("Must be a number"));
+
+        let footer = lines(false);
+        let input_line = footer.iter().find(|line| line.contains("abc")).unwrap();
+        assert!(!input_line.contains("Must be a number"));
+        assert!(footer.last().unwrap().contains("Must be a number"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn finalize_redraws_on_the_writer_of_the_prompt_wrapped() {
+        let mut input = Input::new("Name").preset_answer("my-app-".repeat(8));
+        let mut out = Vec::new();
+        let (drawn, output) = crate::output::capture(|| {
+            crate::set_max_width(30);
+            input.interact_with_writer::<String, _>(&mut out).unwrap();
+            let drawn = String::from_utf8(out.clone()).unwrap();
+            input.finalize_with_writer(&mut out, true).unwrap();
+            crate::set_max_width(0);
+            drawn
+        });
+        assert!(
+            !output.contains("my-app"),
+            "nothing is drawn on the terminal"
+        );
+
+        let out = String::from_utf8(out).unwrap();
+        let finalized = &out[drawn.len()..];
+        let height = drawn.lines().count();
+        assert!(height > 3, "the long answer is wrapped");
+        assert!(
+            finalized.starts_with(&format!("\x1b[{height}A")),
+            "{finalized:?}"
+        );
+        let status = THEME.lock().unwrap().format_status(true);
+        assert!(finalized.contains(&status), "{finalized:?}");
+    }
+}
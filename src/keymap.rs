@@ -0,0 +1,338 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use console::Key;
+use once_cell::sync::Lazy;
+
+/// A logical action which can be bound to the keys in a [`KeyMap`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    /// Moves the cursor to the previous item, or toggles the confirmation.
+    MoveUp,
+    /// Moves the cursor to the next item, or toggles the confirmation.
+    MoveDown,
+    /// Checks or unchecks the item of a multiselect, or toggles the confirmation.
+    Toggle,
+    /// Submits the prompt.
+    Submit,
+    /// Cancels the prompt unless it's not cancelable, `Esc` cancels it regardless
+    /// of the key map.
+    Cancel,
+    /// Jumps to the first item of the list.
+    First,
+    /// Jumps to the last item of the list.
+    Last,
+}
+
+/// Maps the keys and multi-key chords (like `g g`) to logical actions.
+///
+/// The key map is consulted by [`Select`](crate::Select), [`MultiSelect`](crate::MultiSelect)
+/// and [`Confirm`](crate::Confirm), either the global one set with [`set_keymap`]
+/// or the one set per prompt.
+///
+/// ```
+/// use cliclack::{Action, KeyMap};
+/// use console::Key;
+///
+/// let keymap = KeyMap::default()
+///     .bind([Key::Char('g'), Key::Char('g')], Action::First)
+///     .bind([Key::Char('G')], Action::Last);
+/// ```
+#[derive(Clone)]
+pub struct KeyMap {
+    bindings: Vec<(Vec<Key>, Action)>,
+    chord_timeout: Duration,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        Self {
+            bindings: vec![
+                (vec![Key::ArrowUp], Action::MoveUp),
+                (vec![Key::ArrowDown], Action::MoveDown),
+                (vec![Key::Char(' ')], Action::Toggle),
+                (vec![Key::Enter], Action::Submit),
+                (vec![Key::Home], Action::First),
+                (vec![Key::End], Action::Last),
+            ],
+            chord_timeout: Duration::from_millis(1000),
+        }
+    }
+}
+
+impl KeyMap {
+    /// Creates a key map with the default bindings of the arrows, `Space`,
+    /// `Enter`, `Home` and `End`, see [`KeyMap::default`], so the prompts can
+    /// still be navigated and submitted. The keys can be bound anew then.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the key map of the vim-like navigation: `j` and `k` move
+    /// down and up, `g g` and `G` jump to the first and the last item
+    /// and `q` cancels, in addition to the default bindings.
+    ///
+    /// The bound letters are not used for the type-ahead search then, except
+    /// a single `g` which is replayed once the [`KeyMap::chord_timeout`] elapses.
+    pub fn vim() -> Self {
+        Self::default()
+            .bind([Key::Char('j')], Action::MoveDown)
+            .bind([Key::Char('k')], Action::MoveUp)
+            .bind([Key::Char('g'), Key::Char('g')], Action::First)
+            .bind([Key::Char('G')], Action::Last)
+            .bind([Key::Char('q')], Action::Cancel)
+    }
+
+    /// Binds the key sequence to the action, replacing its previous binding.
+    /// A single key is a sequence of one.
+    pub fn bind(mut self, keys: impl IntoIterator<Item = Key>, action: Action) -> Self {
+        let keys = keys.into_iter().collect::<Vec<_>>();
+        if !keys.is_empty() {
+            self.bindings.retain(|(bound, _)| bound != &keys);
+            self.bindings.push((keys, action));
+        }
+        self
+    }
+
+    /// Sets the maximum delay between the keys of a chord, after which
+    /// the typed part of the chord is replayed as the separate keys, e.g. to
+    /// the type-ahead search. Default: 1 second.
+    pub fn chord_timeout(mut self, timeout: Duration) -> Self {
+        self.chord_timeout = timeout;
+        self
+    }
+
+    /// Returns the action bound to the single key, ignoring the chords.
+    pub(crate) fn action(&self, key: &Key) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(keys, _)| keys.len() == 1 && keys[0] == *key)
+            .map(|(_, action)| *action)
+    }
+}
+
+/// The global key map instance (singleton).
+///
+/// It can be set with [`set_keymap`] function.
+static KEYMAP: Lazy<Mutex<KeyMap>> = Lazy::new(|| Mutex::new(KeyMap::default()));
+
+/// Sets the global key map, which is used by all prompts without their own one.
+///
+/// ```
+/// use cliclack::{set_keymap, KeyMap};
+///
+/// set_keymap(KeyMap::vim());
+/// ```
+///
+/// See [`reset_keymap`] for returning to the default key map.
+pub fn set_keymap(keymap: KeyMap) {
+    *KEYMAP.lock().unwrap() = keymap;
+}
+
+/// Resets the global key map to [`KeyMap::default`].
+pub fn reset_keymap() {
+    *KEYMAP.lock().unwrap() = KeyMap::default();
+}
+
+/// Returns the key map of the prompt, or the global one if it has none.
+pub(crate) fn keymap_or_global(keymap: &Option<KeyMap>) -> KeyMap {
+    match keymap {
+        Some(keymap) => keymap.clone(),
+        None => KEYMAP.lock().unwrap().clone(),
+    }
+}
+
+/// A key or an action resolved from the typed keys.
+pub(crate) enum Stroke {
+    Key(Key),
+    Action(Action),
+}
+
+/// Tracks the partially typed chord.
+#[derive(Default)]
+pub(crate) struct Chords {
+    pending: Vec<Key>,
+    last: Option<Instant>,
+}
+
+impl Chords {
+    /// Feeds the next key, returns the strokes which are ready to be handled:
+    /// the resolved action, or the keys which don't make up a chord.
+    ///
+    /// The keys of a broken chord are replayed one by one, the last one may
+    /// start another chord.
+    pub fn feed(&mut self, keymap: &KeyMap, key: Key) -> Vec<Stroke> {
+        let mut strokes = self.expire(keymap);
+        self.last = Some(Instant::now());
+        self.resolve(keymap, key, &mut strokes);
+        strokes
+    }
+
+    /// Replays the keys of the partial chord as they are once the chord
+    /// timeout has elapsed, e.g. while no key is pressed.
+    pub fn expire(&mut self, keymap: &KeyMap) -> Vec<Stroke> {
+        match self.last {
+            Some(last) if last.elapsed() >= keymap.chord_timeout => {
+                self.pending.drain(..).map(Stroke::Key).collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Returns the time left until the partial chord expires, if any.
+    pub fn remaining(&self, keymap: &KeyMap) -> Option<Duration> {
+        match self.last {
+            Some(last) if !self.pending.is_empty() => {
+                Some(keymap.chord_timeout.saturating_sub(last.elapsed()))
+            }
+            _ => None,
+        }
+    }
+
+    fn resolve(&mut self, keymap: &KeyMap, key: Key, strokes: &mut Vec<Stroke>) {
+        self.pending.push(key);
+
+        let mut prefix = false;
+        for (keys, action) in &keymap.bindings {
+            if *keys == self.pending {
+                self.pending.clear();
+                strokes.push(Stroke::Action(*action));
+                return;
+            }
+            prefix |= keys.starts_with(&self.pending);
+        }
+        if prefix {
+            return;
+        }
+
+        // The chord is broken by the last key which is resolved on its own.
+        let broken = self.pending.len() > 1;
+        let key = self.pending.pop();
+        strokes.extend(self.pending.drain(..).map(Stroke::Key));
+        match key {
+            Some(key) if broken => self.resolve(keymap, key, strokes),
+            Some(key) => strokes.push(Stroke::Key(key)),
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn actions(strokes: &[Stroke]) -> Vec<Option<Action>> {
+        strokes
+            .iter()
+            .map(|stroke| match stroke {
+                Stroke::Action(action) => Some(*action),
+                Stroke::Key(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn chord_fires_the_bound_action() {
+        let keymap = KeyMap::vim();
+        let mut chords = Chords::default();
+
+        assert!(chords.feed(&keymap, Key::Char('g')).is_empty(), "pending");
+        let strokes = chords.feed(&keymap, Key::Char('g'));
+        assert_eq!(actions(&strokes), [Some(Action::First)]);
+        let strokes = chords.feed(&keymap, Key::Char('G'));
+        assert_eq!(actions(&strokes), [Some(Action::Last)]);
+    }
+
+    #[test]
+    fn broken_chord_passes_the_keys_through() {
+        let keymap = KeyMap::vim();
+        let mut chords = Chords::default();
+
+        chords.feed(&keymap, Key::Char('g'));
+        let strokes = chords.feed(&keymap, Key::Char('x'));
+        assert!(matches!(
+            strokes.as_slice(),
+            [Stroke::Key(Key::Char('g')), Stroke::Key(Key::Char('x'))]
+        ));
+    }
+
+    #[test]
+    fn chord_timeout_resets_the_partial_chord() {
+        let keymap = KeyMap::vim().chord_timeout(Duration::from_millis(10));
+        let mut chords = Chords::default();
+
+        chords.feed(&keymap, Key::Char('g'));
+        std::thread::sleep(Duration::from_millis(30));
+        let strokes = chords.feed(&keymap, Key::Char('g'));
+        assert!(matches!(strokes.as_slice(), [Stroke::Key(Key::Char('g'))]));
+
+        let strokes = chords.feed(&keymap, Key::Char('g'));
+        assert_eq!(actions(&strokes), [Some(Action::First)], "a new chord");
+    }
+
+    #[test]
+    fn single_key_actions_ignore_the_chords() {
+        let keymap = KeyMap::vim();
+        assert_eq!(keymap.action(&Key::Enter), Some(Action::Submit));
+        assert_eq!(keymap.action(&Key::Char('j')), Some(Action::MoveDown));
+        assert_eq!(keymap.action(&Key::Char('g')), None);
+    }
+
+    #[test]
+    fn new_key_map_keeps_the_default_bindings_until_rebound() {
+        let keymap = KeyMap::new();
+        assert_eq!(keymap.action(&Key::Enter), Some(Action::Submit));
+        assert_eq!(keymap.action(&Key::ArrowDown), Some(Action::MoveDown));
+
+        let keymap = keymap
+            .bind([Key::Char('l')], Action::Submit)
+            .bind([Key::Enter], Action::Toggle);
+        assert_eq!(keymap.action(&Key::Char('l')), Some(Action::Submit));
+        assert_eq!(keymap.action(&Key::Enter), Some(Action::Toggle));
+    }
+
+    #[test]
+    fn key_breaking_a_chord_can_start_another_one() {
+        let keymap = KeyMap::new()
+            .bind([Key::Char('g'), Key::Char('g')], Action::First)
+            .bind([Key::Char('z'), Key::Char('z')], Action::Last);
+        let mut chords = Chords::default();
+
+        chords.feed(&keymap, Key::Char('g'));
+        let strokes = chords.feed(&keymap, Key::Char('z'));
+        assert!(matches!(strokes.as_slice(), [Stroke::Key(Key::Char('g'))]));
+        let strokes = chords.feed(&keymap, Key::Char('z'));
+        assert_eq!(actions(&strokes), [Some(Action::Last)]);
+
+        chords.feed(&keymap, Key::Char('g'));
+        let strokes = chords.feed(&keymap, Key::ArrowDown);
+        assert!(matches!(
+            strokes.as_slice(),
+            [
+                Stroke::Key(Key::Char('g')),
+                Stroke::Action(Action::MoveDown)
+            ]
+        ));
+    }
+
+    #[test]
+    fn partial_chord_is_replayed_once_it_expires() {
+        let keymap = KeyMap::vim().chord_timeout(Duration::from_millis(10));
+        let mut chords = Chords::default();
+        assert_eq!(chords.remaining(&keymap), None);
+
+        chords.feed(&keymap, Key::Char('g'));
+        assert!(chords.remaining(&keymap).is_some());
+        assert!(
+            chords.expire(&keymap).is_empty(),
+            "the chord is still pending"
+        );
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(chords.remaining(&keymap), Some(Duration::ZERO));
+        let strokes = chords.expire(&keymap);
+        assert!(matches!(strokes.as_slice(), [Stroke::Key(Key::Char('g'))]));
+        assert_eq!(chords.remaining(&keymap), None);
+        assert!(chords.expire(&keymap).is_empty(), "replayed only once");
+    }
+}
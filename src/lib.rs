@@ -34,9 +34,13 @@
 //!
 //! ## Cancellation
 //!
-//! `Esc` cancels the prompt sequence with a nice message.
-//! `Ctrl+C` interrupts the session abruptly, it's handled inside of the
-//! `Term` crate and cannot be easily caught and rendered fancy.
+//! `Esc` cancels the prompt sequence with a nice message, unless the prompt
+//! is set up with `no_cancel`.
+//! `Ctrl+C` cancels the prompt the same way, but regardless of `no_cancel`,
+//! so the user can always quit. It's not a signal killing the process:
+//! the prompt returns [`io::ErrorKind::Interrupted`] in both cases once the
+//! terminal is restored, and the caller decides what to print, e.g. with
+//! [`outro_cancel`].
 //!
 //! # Components
 //!
@@ -199,41 +203,198 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs, unused_qualifications)]
 
+mod autocomplete;
 mod confirm;
+mod error;
+mod group;
 mod input;
+mod keymap;
+#[cfg(feature = "log")]
+mod logger;
+mod multiline;
 mod multiselect;
+mod number;
+mod output;
 mod password;
+mod progress;
 mod prompt;
 mod select;
+mod session;
 mod spinner;
+mod state;
 mod theme;
 mod validate;
 
 use console::Term;
 use std::fmt::Display;
 use std::io;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 use theme::THEME;
 
 // 🎨 Re-export of the theme API.
-pub use theme::{reset_theme, set_theme, Theme, ThemeState};
+pub use theme::{
+    reset_theme, set_terminal_background, set_theme, terminal_background, AdaptiveTheme,
+    ColorblindTheme, SelectListItem, SelectListLayout, TerminalBackground, Theme, ThemeState,
+};
 
+pub use autocomplete::Autocomplete;
 pub use confirm::Confirm;
+pub use error::PromptError;
+pub use group::{Answers, Group};
 pub use input::Input;
+pub use keymap::{reset_keymap, set_keymap, Action, KeyMap};
+pub use multiline::MultilineInput;
 pub use multiselect::MultiSelect;
-pub use password::Password;
-pub use select::Select;
+pub use number::{Number, NumberStep};
+pub use output::{reset_output, set_output};
+pub use password::{Password, PasswordStrength};
+pub use progress::{MultiProgress, ProgressBar};
+pub use prompt::interaction::TimeoutAction;
+pub use select::{Select, SelectItems, SelectStyle};
+pub use session::Session;
 pub use spinner::Spinner;
+pub use state::{reset_state_provider, set_state_provider, StateProvider};
 pub use validate::Validate;
 
+#[cfg(feature = "async")]
+pub use prompt::interaction::KeyStream;
+
+#[cfg(feature = "log")]
+pub use logger::LogBridge;
+
 fn term_write(line: String) -> io::Result<()> {
-    Term::stderr().write_str(&line)
+    output::term().write_str(&line)
+}
+
+/// Wraps the text to fit the maximum width set with [`set_max_width`]
+/// leaving the margin for the frame decorations.
+fn fit_max_width(text: impl Display, margin: usize) -> String {
+    match prompt::interaction::MAX_WIDTH.load(Ordering::Relaxed) {
+        0 => text.to_string(),
+        max_width => textwrap::fill(&text.to_string(), max_width.saturating_sub(margin).max(1)),
+    }
+}
+
+/// Limits the width of the whole UI, e.g. to `80` columns on ultrawide terminals.
+///
+/// Prompts, notes and log messages are wrapped to fit the width regardless
+/// of the terminal size. `0` resets the limit to the terminal width.
+pub fn set_max_width(width: usize) {
+    prompt::interaction::MAX_WIDTH.store(width, Ordering::Relaxed);
+}
+
+/// Keeps every spinner shown for at least the given duration, e.g. `300ms`,
+/// so a task finishing quickly doesn't flash the spinner before the next
+/// prompt. Default: zero, the spinner stops immediately.
+///
+/// Stopping the spinner earlier waits for the rest of the duration.
+pub fn set_min_spinner_duration(duration: Duration) {
+    let millis = duration.as_millis().try_into().unwrap_or(u64::MAX);
+    spinner::MIN_DURATION.store(millis, Ordering::Relaxed);
 }
 
 /// Clears the terminal.
 pub fn clear_screen() -> io::Result<()> {
     Term::stdout().clear_screen()?;
-    Term::stderr().clear_screen()
+    output::term().clear_screen()
+}
+
+/// Hides the terminal cursor, like prompts do during the interaction.
+///
+/// Use [`show_cursor`] to restore it after custom output, or [`hidden_cursor`]
+/// to restore it on panic as well.
+pub fn hide_cursor() -> io::Result<()> {
+    output::term().hide_cursor()
+}
+
+/// Shows the terminal cursor hidden with [`hide_cursor`].
+pub fn show_cursor() -> io::Result<()> {
+    output::term().show_cursor()
+}
+
+/// Runs the custom output with the terminal cursor hidden, showing it again
+/// when the output ends, on panic as well.
+///
+/// ```
+/// # fn test() -> std::io::Result<()> {
+/// use cliclack::hidden_cursor;
+///
+/// hidden_cursor(|| {
+///     eprint!("Loading...");
+///     Ok(())
+/// })?;
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
+pub fn hidden_cursor<R>(output: impl FnOnce() -> io::Result<R>) -> io::Result<R> {
+    let cursor = output::HiddenCursor::new(&output::term())?;
+    let result = output()?;
+    cursor.show()?;
+    Ok(result)
+}
+
+/// Runs the given prompt sequence inside the alternate screen buffer of the
+/// terminal, restoring the prior content when the sequence ends.
+///
+/// The original screen is restored on cancellation and panic as well.
+///
+/// ```
+/// # fn test() -> std::io::Result<()> {
+/// use cliclack::{alternate_screen, input, intro, outro};
+///
+/// let name: String = alternate_screen(|| {
+///     intro("create-my-app")?;
+///     let name = input("Project name").interact()?;
+///     outro("Done")?;
+///     Ok(name)
+/// })?;
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
+pub fn alternate_screen<R>(session: impl FnOnce() -> io::Result<R>) -> io::Result<R> {
+    /// Leaves the alternate screen on drop, e.g. while unwinding a panic.
+    struct AlternateScreen;
+
+    impl Drop for AlternateScreen {
+        fn drop(&mut self) {
+            output::term().write_str("\x1b[?1049l").ok();
+        }
+    }
+
+    output::term().write_str("\x1b[?1049h")?;
+    let _guard = AlternateScreen;
+    session()
+}
+
+/// Enables printing of every key decoded during the prompt interaction
+/// above the prompt. It's useful for debugging "unrecognized key" issues.
+pub fn set_debug_keys(enabled: bool) {
+    prompt::interaction::DEBUG_KEYS.store(enabled, Ordering::Relaxed);
+}
+
+/// Reads the answers of the prompts as lines from stdin if it's not a
+/// terminal, e.g. piped in a script. Default: `false`.
+///
+/// [`Input`], [`Confirm`] (`y`, `yes`, `n`, `no`) and [`Select`] (an item
+/// label) take the line as the answer, an empty line takes the default one.
+/// The other prompts fail with [`io::ErrorKind::InvalidInput`] error, and so
+/// does an invalid answer, the end of stdin is [`io::ErrorKind::UnexpectedEof`].
+///
+/// Without the fallback, the prompts fail with [`io::ErrorKind::NotConnected`]
+/// error if there is no terminal to draw them to.
+pub fn set_stdin_fallback(enabled: bool) {
+    prompt::interaction::STDIN_FALLBACK.store(enabled, Ordering::Relaxed);
+}
+
+/// Forces the Unicode symbols (`true`) or their ASCII fallback (`false`)
+/// regardless of the terminal support, e.g. for consistent screenshots.
+pub fn set_unicode_symbols(enabled: bool) {
+    let value = if enabled { 1 } else { 2 };
+    theme::UNICODE_SYMBOLS.store(value, Ordering::Relaxed);
 }
 
 /// Prints a header of the prompt sequence.
@@ -241,6 +402,28 @@ pub fn intro(title: impl Display) -> io::Result<()> {
     term_write(THEME.lock().unwrap().format_intro(&title.to_string()))
 }
 
+/// Prints a header of the prompt sequence with an icon before the title,
+/// e.g. an emoji of the tool.
+pub fn intro_with_icon(icon: impl Display, title: impl Display) -> io::Result<()> {
+    term_write(
+        THEME
+            .lock()
+            .unwrap()
+            .format_intro_with_icon(&icon.to_string(), &title.to_string()),
+    )
+}
+
+/// Prints a header of the prompt sequence with a dimmed subtitle,
+/// e.g. a version or a description.
+pub fn intro_with_subtitle(title: impl Display, subtitle: impl Display) -> io::Result<()> {
+    term_write(
+        THEME
+            .lock()
+            .unwrap()
+            .format_intro_with_subtitle(&title.to_string(), &subtitle.to_string()),
+    )
+}
+
 /// Prints a footer of the prompt sequence.
 pub fn outro(message: impl Display) -> io::Result<()> {
     term_write(THEME.lock().unwrap().format_outro(&message.to_string()))
@@ -263,6 +446,30 @@ pub fn input(prompt: impl Display) -> Input {
     Input::new(prompt)
 }
 
+/// Constructs a new [`MultilineInput`] prompt.
+///
+/// See [`MultilineInput`] for chainable methods.
+pub fn multiline_input(prompt: impl Display) -> MultilineInput {
+    MultilineInput::new(prompt)
+}
+
+/// Constructs a new [`Number`] prompt.
+///
+/// See [`Number`] for chainable methods.
+pub fn number<T>(prompt: impl Display) -> Number<T>
+where
+    T: std::str::FromStr + PartialOrd + Display + Clone,
+{
+    Number::new(prompt)
+}
+
+/// Constructs a new [`Autocomplete`] prompt.
+///
+/// See [`Autocomplete`] for chainable methods.
+pub fn autocomplete(prompt: impl Display) -> Autocomplete {
+    Autocomplete::new(prompt)
+}
+
 /// Constructs a new [`Password`] prompt.
 ///
 /// See [`Password`] for chainable methods.
@@ -291,6 +498,13 @@ pub fn confirm(prompt: impl Display) -> Confirm {
     Confirm::new(prompt)
 }
 
+/// Constructs a new [`Group`] of prompts under the intro with the given title.
+///
+/// See [`Group`] for chainable methods.
+pub fn group<'a>(title: impl Display) -> Group<'a> {
+    Group::new(title)
+}
+
 /// Constructs a new [`Spinner`] prompt.
 ///
 /// See [`Spinner`] for chainable methods.
@@ -298,14 +512,61 @@ pub fn spinner() -> Spinner {
     Spinner::default()
 }
 
+/// Constructs a new [`ProgressBar`].
+///
+/// See [`ProgressBar`] for the methods.
+pub fn progress_bar() -> ProgressBar {
+    ProgressBar::default()
+}
+
+fn note_with_state(
+    state: ThemeState,
+    prompt: impl Display,
+    message: impl Display,
+) -> io::Result<()> {
+    term_write(THEME.lock().unwrap().format_note(
+        &state,
+        &fit_max_width(prompt, 7),
+        &fit_max_width(message, 7),
+    ))
+}
+
 /// Prints a note message.
 pub fn note(prompt: impl Display, message: impl Display) -> io::Result<()> {
-    term_write(
-        THEME
-            .lock()
-            .unwrap()
-            .format_note(&prompt.to_string(), &message.to_string()),
-    )
+    note_with_state(ThemeState::Submit, prompt, message)
+}
+
+/// Prints a note message with a warning style.
+pub fn note_warning(prompt: impl Display, message: impl Display) -> io::Result<()> {
+    note_with_state(ThemeState::Error(String::new()), prompt, message)
+}
+
+/// Prints a note message with an error style.
+pub fn note_error(prompt: impl Display, message: impl Display) -> io::Result<()> {
+    note_with_state(ThemeState::Cancel, prompt, message)
+}
+
+/// Prints a note message nested into the frame bars of the given level,
+/// e.g. a warning inside a section, `0` is the same as [`note`].
+///
+/// The message is wrapped to fit [`set_max_width`] including the indentation.
+///
+/// ```
+/// # fn test() -> std::io::Result<()> {
+/// cliclack::log::step("Deploying")?;
+/// cliclack::note_nested(1, "Warning", "The cache is cold")?;
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
+pub fn note_nested(level: usize, prompt: impl Display, message: impl Display) -> io::Result<()> {
+    let margin = 7 + 3 * level;
+    term_write(THEME.lock().unwrap().format_note_nested(
+        &ThemeState::Submit,
+        level,
+        &fit_max_width(prompt, margin),
+        &fit_max_width(message, margin),
+    ))
 }
 
 /// Non-interactive information messages of different styles.
@@ -317,7 +578,7 @@ pub mod log {
             THEME
                 .lock()
                 .unwrap()
-                .format_log(&text.to_string(), &symbol.to_string()),
+                .format_log(&fit_max_width(text, 3), &symbol.to_string()),
         )
     }
 
@@ -356,4 +617,161 @@ pub mod log {
         let symbol = THEME.lock().unwrap().submit_symbol();
         log(message, symbol)
     }
+
+    /// Prints a dimmed message of a skipped step, e.g. of a wizard.
+    pub fn skip(message: impl Display) -> io::Result<()> {
+        let symbol = THEME.lock().unwrap().skip_symbol();
+        log(console::style(message.to_string()).dim(), symbol)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_helpers_emit_the_escape_sequences() {
+        let ((), output) = output::capture(|| {
+            hide_cursor().unwrap();
+            show_cursor().unwrap();
+        });
+        assert_eq!(output, "\x1b[?25l\x1b[?25h");
+    }
+
+    #[test]
+    fn hidden_cursor_is_shown_again_on_panic() {
+        let (result, output) = output::capture(|| {
+            std::panic::catch_unwind(|| hidden_cursor(|| -> io::Result<()> { panic!("oops") }))
+        });
+        assert!(result.is_err());
+        assert_eq!(output, "\x1b[?25l\x1b[?25h");
+    }
+
+    #[test]
+    fn alternate_screen_brackets_the_session() {
+        let (result, output) = output::capture(|| {
+            alternate_screen(|| {
+                intro("app")?;
+                Ok(42)
+            })
+        });
+        assert_eq!(result.unwrap(), 42);
+        assert!(output.starts_with("\x1b[?1049h"));
+        assert!(output.ends_with("\x1b[?1049l"));
+        assert!(output.contains("app"));
+    }
+
+    #[test]
+    fn alternate_screen_is_left_on_cancel_and_panic() {
+        let (result, output) = output::capture(|| {
+            alternate_screen(|| -> io::Result<()> { Err(io::ErrorKind::Interrupted.into()) })
+        });
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::Interrupted);
+        assert_eq!(output, "\x1b[?1049h\x1b[?1049l");
+
+        let (result, output) = output::capture(|| {
+            std::panic::catch_unwind(|| alternate_screen(|| -> io::Result<()> { panic!("oops") }))
+        });
+        assert!(result.is_err());
+        assert_eq!(output, "\x1b[?1049h\x1b[?1049l");
+    }
+
+    #[test]
+    fn notes_and_inputs_respect_the_max_width() {
+        let (answer, output) = output::capture(|| {
+            set_max_width(40);
+            note("Note", "word ".repeat(30)).unwrap();
+            let answer = input("Name")
+                .preset_answer("x".repeat(100))
+                .interact::<String>();
+            set_max_width(0);
+            answer
+        });
+        assert_eq!(answer.unwrap().len(), 100);
+        assert!(output.contains("word"));
+        assert!(
+            output
+                .lines()
+                .all(|line| console::measure_text_width(line) <= 40),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn skipped_step_is_dimmed_with_the_skip_symbol() {
+        console::set_colors_enabled(true);
+        let ((), output) = output::capture(|| log::skip("Database setup").unwrap());
+
+        let symbol = THEME.lock().unwrap().skip_symbol();
+        assert!(symbol.starts_with("\x1b[2m"), "{symbol:?}");
+        assert!(output.starts_with(&symbol), "{output:?}");
+        assert!(output.contains(&console::style("Database setup").dim().to_string()));
+    }
+
+    #[test]
+    fn ascii_symbols_are_forced_across_the_prompts() {
+        let (_, output) = output::capture(|| {
+            set_unicode_symbols(false);
+            intro("app").unwrap();
+            let name = input("Name").preset_answer("my-app").interact::<String>();
+            let template = select("Template")
+                .item("web", "Web", "")
+                .preset_answer("web")
+                .interact();
+            outro("Done").unwrap();
+            theme::UNICODE_SYMBOLS.store(0, Ordering::Relaxed);
+            (name, template)
+        });
+
+        let output = console::strip_ansi_codes(&output);
+        for symbol in ["┌", "│", "◇", "└"] {
+            assert!(!output.contains(symbol), "{symbol} in {output}");
+        }
+        assert!(output.starts_with("T  app"), "{output}");
+        assert!(output.contains("o  Name"), "{output}");
+        assert!(output.contains("o  Template"), "{output}");
+        assert!(output.contains("\n|  my-app\n"), "{output}");
+    }
+
+    #[test]
+    fn nested_note_fits_the_max_width_with_the_indentation() {
+        let ((), output) = output::capture(|| {
+            set_max_width(40);
+            note_nested(2, "Warning", "word ".repeat(30)).unwrap();
+            set_max_width(0);
+        });
+
+        let output = console::strip_ansi_codes(&output);
+        let widths = output
+            .lines()
+            .map(console::measure_text_width)
+            .collect::<Vec<_>>();
+        assert!(widths.iter().all(|width| *width <= 40), "{output}");
+        assert!(widths.len() > 3, "the message is wrapped: {output}");
+    }
+
+    #[test]
+    fn intro_icon_precedes_the_title() {
+        let ((), output) = output::capture(|| intro_with_icon("🚀", "create-app").unwrap());
+        let ((), plain) = output::capture(|| intro("create-app").unwrap());
+
+        let output = console::strip_ansi_codes(&output);
+        let first = output.lines().next().unwrap();
+        assert!(first.ends_with("  🚀 create-app"), "{first:?}");
+        assert_eq!(output.lines().count(), plain.lines().count());
+    }
+
+    #[test]
+    fn set_output_redirects_the_messages_and_the_progress_footer() {
+        let ((), output) = output::capture(|| {
+            intro("create-app").unwrap();
+            log::info("Fetching").unwrap();
+            MultiProgress::default().stop("Fetched").unwrap();
+        });
+
+        let output = console::strip_ansi_codes(&output);
+        for message in ["create-app", "Fetching", "Fetched"] {
+            assert!(output.contains(message), "{output}");
+        }
+    }
 }
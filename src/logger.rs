@@ -0,0 +1,106 @@
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+use crate::{spinner, theme::THEME};
+
+/// A [`log`](https://docs.rs/log) backend which prints the records as
+/// the themed [`log`](crate::log) messages.
+///
+/// A record emitted while a [`Spinner`](crate::Spinner) is running is printed
+/// above it, the spinner is suspended for the time and drawn again below.
+///
+/// ```
+/// use cliclack::LogBridge;
+/// use log::LevelFilter;
+///
+/// LogBridge::new(LevelFilter::Info).init().ok();
+///
+/// let mut spinner = cliclack::spinner();
+/// spinner.start("Installing...");
+/// log::info!("Fetched 42 packages");
+/// spinner.stop("Installed");
+/// ```
+pub struct LogBridge {
+    level: LevelFilter,
+}
+
+impl LogBridge {
+    /// Creates the bridge printing the records up to the given level.
+    pub fn new(level: LevelFilter) -> Self {
+        Self { level }
+    }
+
+    /// Sets the bridge as the global logger with its maximum level.
+    pub fn init(self) -> Result<(), SetLoggerError> {
+        let level = self.level;
+        log::set_boxed_logger(Box::new(self))?;
+        log::set_max_level(level);
+        Ok(())
+    }
+}
+
+impl Log for LogBridge {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let text = {
+            let theme = THEME.lock().unwrap();
+            let symbol = match record.level() {
+                Level::Error => theme.error_symbol(),
+                Level::Warn => theme.warning_symbol(),
+                Level::Info => theme.info_symbol(),
+                Level::Debug | Level::Trace => theme.remark_symbol(),
+            };
+            theme.format_log(&record.args().to_string(), &symbol)
+        };
+
+        // A logger has nowhere to report its own failure.
+        spinner::write_above(&text).ok();
+    }
+
+    fn flush(&self) {}
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::output::{self, Recorder};
+
+    #[test]
+    fn records_are_printed_above_the_running_spinner() {
+        let recorder = Recorder::default();
+        let mut spinner = spinner();
+        spinner.draw_to(recorder.target());
+        spinner.start("Installing");
+
+        let bridge = LogBridge::new(LevelFilter::Info);
+        let ((), output) = output::capture(|| {
+            let drawn = recorder.text().len();
+            bridge.log(
+                &Record::builder()
+                    .level(Level::Warn)
+                    .args(format_args!("Deprecated package"))
+                    .build(),
+            );
+            bridge.log(
+                &Record::builder()
+                    .level(Level::Debug)
+                    .args(format_args!("Filtered out"))
+                    .build(),
+            );
+
+            let redrawn = &recorder.text()[drawn..];
+            let cleared = redrawn.find("<clear>").expect("the spinner is cleared");
+            assert!(redrawn[cleared..].contains("Installing"), "{redrawn}");
+        });
+
+        assert!(output.contains("Deprecated package"), "{output}");
+        assert!(!output.contains("Filtered out"));
+        spinner.stop("Installed");
+    }
+}
@@ -0,0 +1,350 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use console::{Key, Term};
+
+use crate::{
+    prompt::{
+        cursor::StringCursor,
+        interaction::{Event, PromptInteraction, State, Timeout, TimeoutAction},
+    },
+    state::stored_answer,
+    theme::THEME,
+    validate::Validate,
+};
+
+type ValidationCallback = Box<dyn Fn(&String) -> Result<(), String>>;
+
+/// A prompt that accepts a multiline text, e.g. a commit message.
+///
+/// `Enter` inserts a new line, `Ctrl+D` submits the text, `Up` and `Down`
+/// arrows move the cursor across the lines, `Home` and `End` within the line.
+///
+/// # Example
+///
+/// ```
+/// use cliclack::MultilineInput;
+///
+/// # fn test() -> std::io::Result<()> {
+/// let message = MultilineInput::new("Commit message")
+///     .placeholder("Summary, blank line, details")
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
+pub struct MultilineInput {
+    prompt: String,
+    input: StringCursor,
+    placeholder: StringCursor,
+    prompt_id: Option<String>,
+    input_required: bool,
+    validate: Option<ValidationCallback>,
+    submit_key: Key,
+    no_cancel: bool,
+    flush_input: bool,
+    timeout: Timeout,
+    help: Option<String>,
+}
+
+impl MultilineInput {
+    /// Creates a new multiline input prompt.
+    pub fn new(prompt: impl Display) -> Self {
+        Self {
+            prompt: prompt.to_string(),
+            input: StringCursor::default(),
+            placeholder: StringCursor::default(),
+            prompt_id: None,
+            input_required: true,
+            validate: None,
+            // Ctrl+D
+            submit_key: Key::Char('\u{4}'),
+            no_cancel: false,
+            flush_input: false,
+            timeout: Timeout::default(),
+            help: None,
+        }
+    }
+
+    /// Sets the placeholder (hint) text for the input.
+    pub fn placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder.extend(placeholder);
+        self
+    }
+
+    /// Sets the initial text of the input, the cursor is placed at its end.
+    pub fn default_input(mut self, value: &str) -> Self {
+        self.input.extend(value);
+        self.input.move_end();
+        self
+    }
+
+    /// Sets the identifier of the prompt to look up the previous answer
+    /// with the global [`StateProvider`](crate::StateProvider).
+    ///
+    /// The stored answer is used as the initial text only if
+    /// [`MultilineInput::default_input`] is not set.
+    pub fn prompt_id(mut self, id: &str) -> Self {
+        self.prompt_id = Some(id.into());
+        self
+    }
+
+    /// Sets whether the input is required. Default: `true`.
+    pub fn required(mut self, required: bool) -> Self {
+        self.input_required = required;
+        self
+    }
+
+    /// Sets a validation callback for the text.
+    pub fn validate<V>(mut self, validator: V) -> Self
+    where
+        V: Validate<String> + 'static,
+        V::Err: ToString,
+    {
+        self.validate = Some(Box::new(move |input: &String| {
+            validator.validate(input).map_err(|err| err.to_string())
+        }));
+        self
+    }
+
+    /// Sets the key submitting the text. Default: `Ctrl+D`.
+    pub fn submit_key(mut self, key: Key) -> Self {
+        self.submit_key = key;
+        self
+    }
+
+    /// Sets the help text which is shown in a box above the prompt on `?`.
+    ///
+    /// `?` is not typed into the input then.
+    pub fn help(mut self, help: impl Display) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+
+    /// Resolves the prompt with the [`MultilineInput::on_timeout`] action if no key
+    /// is pressed for the duration, the countdown restarts on every key.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout.duration = Some(timeout);
+        self
+    }
+
+    /// Sets what happens on the [`MultilineInput::timeout`]. Default: [`TimeoutAction::Cancel`].
+    pub fn on_timeout(mut self, action: TimeoutAction) -> Self {
+        self.timeout.action = action;
+        self
+    }
+
+    /// Ignores `Esc`, so the prompt can only be submitted. Default: `false`.
+    pub fn no_cancel(mut self, no_cancel: bool) -> Self {
+        self.no_cancel = no_cancel;
+        self
+    }
+
+    /// Discards the keys typed before the prompt shows up, so they can't
+    /// answer it by accident. Default: `false`, the keys typed ahead are kept.
+    pub fn flush_input(mut self, flush: bool) -> Self {
+        self.flush_input = flush;
+        self
+    }
+
+    /// Starts the prompt interaction.
+    pub fn interact(&mut self) -> io::Result<String> {
+        <Self as PromptInteraction<String>>::interact(self)
+    }
+
+    /// Starts the prompt interaction returning a [`PromptError`](crate::PromptError)
+    /// which tells the cancellation apart from the I/O failures.
+    pub fn interact2(&mut self) -> Result<String, crate::PromptError> {
+        Ok(self.interact()?)
+    }
+
+    /// Starts the prompt interaction on the given terminal instead of stderr.
+    pub fn interact_on(&mut self, term: &Term) -> io::Result<String> {
+        <Self as PromptInteraction<String>>::interact_on(self, &mut term.clone())
+    }
+
+    /// Starts the prompt interaction awaiting the keys from the stream.
+    #[cfg(feature = "async")]
+    pub async fn interact_async<S: crate::KeyStream>(
+        &mut self,
+        keys: &mut S,
+    ) -> io::Result<String> {
+        <Self as PromptInteraction<String>>::interact_async(self, keys).await
+    }
+
+    /// Returns the number of lines the prompt occupies on the terminal of
+    /// the given width, e.g. to reserve the space in a composite UI.
+    pub fn rendered_height(&mut self, width: usize) -> usize {
+        <Self as PromptInteraction<String>>::rendered_height(self, &State::Active, width)
+    }
+
+    /// Starts the prompt interaction drawing it to the given writer.
+    ///
+    /// The keys are read from the terminal, see [`MultilineInput::interact`].
+    pub fn interact_with_writer<W: Write>(&mut self, writer: &mut W) -> io::Result<String> {
+        <Self as PromptInteraction<String>>::interact_with_writer(self, writer)
+    }
+}
+
+impl PromptInteraction<String> for MultilineInput {
+    fn init(&mut self) {
+        if self.input.is_empty() {
+            if let Some(answer) = stored_answer(&self.prompt_id) {
+                self.input.extend(&answer);
+                self.input.move_end();
+            }
+        }
+    }
+
+    fn cancelable(&self) -> bool {
+        !self.no_cancel
+    }
+
+    fn flush_input(&self) -> bool {
+        self.flush_input
+    }
+
+    fn timeout(&self) -> Timeout {
+        self.timeout
+    }
+
+    fn on_timeout(&mut self) -> State<String> {
+        let submit = self.submit_key.clone();
+        self.on(&Event::Key(submit))
+    }
+
+    fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    fn input(&mut self) -> Option<&mut StringCursor> {
+        Some(&mut self.input)
+    }
+
+    fn multiline(&self) -> bool {
+        true
+    }
+
+    fn on(&mut self, event: &Event) -> State<String> {
+        let Event::Key(key) = event;
+
+        if *key == self.submit_key {
+            if self.input.is_empty() && self.input_required {
+                return State::Error("Input required".to_string());
+            }
+
+            let text = self.input.to_string();
+            if let Some(validator) = &self.validate {
+                if let Err(err) = validator(&text) {
+                    return State::Error(err);
+                }
+            }
+            return State::Submit(text);
+        }
+
+        match key {
+            Key::Enter => self.input.insert('\n'),
+            Key::ArrowUp => self.input.move_line_up(),
+            Key::ArrowDown => self.input.move_line_down(),
+            _ => {}
+        }
+
+        State::Active
+    }
+
+    fn render(&mut self, state: &State<String>) -> String {
+        let theme = THEME.lock().unwrap();
+
+        let line1 = theme.format_header(&state.into(), &self.prompt);
+        let line2 = if self.input.is_empty() {
+            theme.format_placeholder(&state.into(), &self.placeholder)
+        } else {
+            theme.format_multiline_input(&state.into(), &self.input)
+        };
+        let line3 = theme.format_footer(&state.into());
+
+        line1 + &line2 + &line3
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(input: &mut MultilineInput, text: &str) {
+        for chr in text.chars() {
+            let key = match chr {
+                '\n' => Key::Enter,
+                chr => Key::Char(chr),
+            };
+            input.handle_key(key);
+        }
+    }
+
+    fn submit(input: &mut MultilineInput) -> Option<String> {
+        input.handle_key(Key::Char('\u{4}')).submitted()
+    }
+
+    #[test]
+    fn enter_inserts_a_newline_and_backspace_deletes_it() {
+        let mut input = MultilineInput::new("Message");
+        type_text(&mut input, "ab\n");
+        input.handle_key(Key::Backspace);
+        type_text(&mut input, "c\nd");
+        assert_eq!(submit(&mut input).as_deref(), Some("abc\nd"));
+    }
+
+    #[test]
+    fn arrows_move_across_the_lines() {
+        let mut input = MultilineInput::new("Message");
+        type_text(&mut input, "abcdef\nxy");
+
+        // The column 2 fits the longer line above.
+        input.handle_key(Key::ArrowUp);
+        type_text(&mut input, "-");
+        // The column 3 doesn't fit the shorter line below.
+        input.handle_key(Key::ArrowDown);
+        type_text(&mut input, "+");
+        assert_eq!(submit(&mut input).as_deref(), Some("ab-cdef\nxy+"));
+    }
+
+    #[test]
+    fn home_and_end_move_within_the_current_line() {
+        let mut input = MultilineInput::new("Message");
+        type_text(&mut input, "abc\ndef");
+        input.handle_key(Key::ArrowLeft);
+
+        input.handle_key(Key::Home);
+        type_text(&mut input, "<");
+        input.handle_key(Key::End);
+        type_text(&mut input, ">");
+        assert_eq!(submit(&mut input).as_deref(), Some("abc\n<def>"));
+    }
+
+    #[test]
+    fn cursor_is_drawn_on_its_line_and_the_footer_after_the_last_one() {
+        console::set_colors_enabled(true);
+        let mut input = MultilineInput::new("Message");
+        type_text(&mut input, "one\ntwo\nthree");
+        input.handle_key(Key::ArrowUp);
+
+        let frame = input.render(&State::Active);
+        let lines = frame.lines().collect::<Vec<_>>();
+        let reversed = lines
+            .iter()
+            .position(|line| line.contains("\x1b[7m"))
+            .unwrap();
+        let plain = |i: usize| console::strip_ansi_codes(lines[i]).to_string();
+        assert!(plain(reversed).trim_end().ends_with("two"), "{lines:?}");
+
+        let footer = THEME
+            .lock()
+            .unwrap()
+            .format_footer(&crate::ThemeState::Active);
+        let footer = console::strip_ansi_codes(&footer).trim_end().to_string();
+        assert_eq!(lines.len(), 5, "{lines:?}");
+        assert!(plain(3).ends_with("three"));
+        assert_eq!(plain(4), footer);
+    }
+}
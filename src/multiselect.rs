@@ -1,10 +1,17 @@
 use std::fmt::Display;
-use std::io;
+use std::io::{self, Write};
+use std::time::Duration;
 
-use console::Key;
+use console::{Key, Term};
 
 use crate::{
-    prompt::interaction::{Event, PromptInteraction, State},
+    keymap::{keymap_or_global, Action, Chords, KeyMap, Stroke},
+    prompt::{
+        interaction::{Event, PromptInteraction, State, Timeout, TimeoutAction},
+        repeat::KeyRepeat,
+        typeahead::TypeAhead,
+    },
+    state::stored_answer,
     theme::THEME,
 };
 
@@ -14,6 +21,16 @@ pub struct Checkbox<T: Default> {
     pub label: String,
     pub hint: String,
     pub selected: bool,
+    pub disabled: bool,
+}
+
+type TotalCallback<T> = Box<dyn Fn(&T) -> i64>;
+
+fn plural_items(n: usize) -> &'static str {
+    match n {
+        1 => "item",
+        _ => "items",
+    }
 }
 
 /// A prompt that asks for one or more selections from a list of options.
@@ -23,7 +40,28 @@ pub struct MultiSelect<T: Default> {
     items: Vec<Checkbox<T>>,
     cursor: usize,
     initial_values: Option<Vec<T>>,
+    prompt_id: Option<String>,
     required: bool,
+    selected_first: bool,
+    no_cancel: bool,
+    flush_input: bool,
+    timeout: Timeout,
+    help: Option<String>,
+    summary_separator: Option<String>,
+    summary_max: Option<usize>,
+    type_ahead: TypeAhead,
+    repeat: KeyRepeat,
+    min: Option<usize>,
+    max: Option<usize>,
+    min_error: Option<String>,
+    max_error: Option<String>,
+    toggle_all_key: Option<Key>,
+    keymap: Option<KeyMap>,
+    chords: Chords,
+    preset: Option<Vec<T>>,
+    total: Option<TotalCallback<T>>,
+    preserve_selection_order: bool,
+    selection_order: Vec<usize>,
 }
 
 impl<T> MultiSelect<T>
@@ -46,16 +84,46 @@ where
             label: label.to_string(),
             hint: hint.to_string(),
             selected: false,
+            disabled: false,
+        });
+        self
+    }
+
+    /// Adds an item which is shown greyed out but can't be selected, e.g.
+    /// an option requiring an upgrade. The navigation skips it.
+    ///
+    /// The hint is always shown for the disabled item.
+    pub fn item_disabled(mut self, value: T, label: impl Display, hint: impl Display) -> Self {
+        self.items.push(Checkbox {
+            value,
+            label: label.to_string(),
+            hint: hint.to_string(),
+            selected: false,
+            disabled: true,
         });
         self
     }
 
     /// Sets the initially selected values.
+    ///
+    /// The items are matched by their values, the values without a matching
+    /// item and the disabled items are ignored.
     pub fn initial_values(mut self, value: Vec<T>) -> Self {
         self.initial_values = Some(value);
         self
     }
 
+    /// Sets the identifier of the prompt to look up the initially selected
+    /// items with the global [`StateProvider`](crate::StateProvider).
+    ///
+    /// The stored answer is the labels of the items separated by commas,
+    /// e.g. `ESLint,Prettier`, it's used only if [`MultiSelect::initial_values`]
+    /// is not set.
+    pub fn prompt_id(mut self, id: &str) -> Self {
+        self.prompt_id = Some(id.into());
+        self
+    }
+
     /// Sets whether the input is required. Default: `true` (at least
     /// 1 selected item).
     pub fn required(mut self, required: bool) -> Self {
@@ -63,74 +131,850 @@ where
         self
     }
 
+    /// Requires at least the given number of selected items to submit.
+    pub fn min(mut self, min: usize) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Allows at most the given number of selected items, selecting
+    /// beyond the limit shows an error.
+    pub fn max(mut self, max: usize) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Sets the error shown when fewer items than [`MultiSelect::min`] are selected.
+    /// Default: `Select at least {min} item(s)`.
+    pub fn min_error(mut self, error: impl Display) -> Self {
+        self.min_error = Some(error.to_string());
+        self
+    }
+
+    /// Sets the error shown when more items than [`MultiSelect::max`] are selected.
+    /// Default: `Select at most {max} item(s)`.
+    pub fn max_error(mut self, error: impl Display) -> Self {
+        self.max_error = Some(error.to_string());
+        self
+    }
+
+    /// Shows the sum of the selected items in the header updated live, e.g.
+    /// the total size to stay under a budget. The callback returns the
+    /// contribution of the item by its value.
+    ///
+    /// ```
+    /// use cliclack::MultiSelect;
+    ///
+    /// # fn test() -> std::io::Result<()> {
+    /// let files = MultiSelect::new("Pick the files to upload")
+    ///     .item(120, "photo.jpg", "120 MB")
+    ///     .item(40, "notes.txt", "40 MB")
+    ///     .running_total(|size| *size)
+    ///     .interact()?;
+    /// # Ok(())
+    /// # }
+    /// # test().ok(); // Ignoring I/O runtime errors.
+    /// ```
+    pub fn running_total<F>(mut self, total: F) -> Self
+    where
+        F: Fn(&T) -> i64 + 'static,
+    {
+        self.total = Some(Box::new(total));
+        self
+    }
+
+    /// Sets the key which selects all enabled items, or clears the selection
+    /// if all are selected already, e.g. `Key::Char('a')`. Default: none.
+    ///
+    /// Selecting all items beyond [`MultiSelect::max`] shows an error.
+    pub fn toggle_all_key(mut self, key: Key) -> Self {
+        self.toggle_all_key = Some(key);
+        self
+    }
+
+    /// Returns the selected values in the order they were checked instead of
+    /// the order of the items. Default: `false`.
+    ///
+    /// Unchecking and checking an item again moves it to the end, the initial
+    /// values and the items checked at once keep the order of the items.
+    pub fn preserve_selection_order(mut self, preserve: bool) -> Self {
+        self.preserve_selection_order = preserve;
+        self
+    }
+
+    /// Sets whether the selected items are rendered at the top of the list.
+    /// Default: `false`.
+    ///
+    /// The order is stable within the selected and unselected groups, and
+    /// the returned values keep the original order of the items unless
+    /// [`MultiSelect::preserve_selection_order`] is set.
+    pub fn selected_first(mut self, selected_first: bool) -> Self {
+        self.selected_first = selected_first;
+        self
+    }
+
+    /// Sets the key map of the list navigation, e.g. [`KeyMap::vim`].
+    /// Default: the global one, see [`set_keymap`](crate::set_keymap).
+    pub fn keymap(mut self, keymap: KeyMap) -> Self {
+        self.keymap = Some(keymap);
+        self
+    }
+
+    /// Ignores the arrow keys repeated within the interval after the previous
+    /// one, e.g. `Duration::from_millis(50)`, so a held key on a fast terminal
+    /// doesn't overshoot the selection. Default: zero, all repeats move.
+    pub fn navigation_repeat(mut self, interval: Duration) -> Self {
+        self.repeat.interval(interval);
+        self
+    }
+
+    /// Makes the type-ahead jump to the item by the typed label prefix
+    /// case-sensitive. Default: `false`.
+    pub fn type_ahead_case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.type_ahead.case_sensitive(case_sensitive);
+        self
+    }
+
+    /// Renders the submitted items on a single line joined with the separator.
+    /// Default: each item on its own line.
+    pub fn summary_separator(mut self, separator: &str) -> Self {
+        self.summary_separator = Some(separator.into());
+        self
+    }
+
+    /// Renders the submitted items on a single line truncated after the given
+    /// number of items, like `a, b, … (+3 more)`. Default: no truncation.
+    pub fn summary_max(mut self, max: usize) -> Self {
+        self.summary_max = Some(max);
+        self
+    }
+
+    /// Sets the help text which is shown in a box above the prompt on `?`.
+    pub fn help(mut self, help: impl Display) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+
+    /// Submits the items with the given values without the interaction,
+    /// e.g. for a dry run, drawing only the submitted prompt.
+    ///
+    /// The selection is checked like on `Enter` (see [`MultiSelect::required`],
+    /// [`MultiSelect::min`] and [`MultiSelect::max`]), a failure is returned
+    /// as [`io::ErrorKind::InvalidInput`] error.
+    pub fn preset_answer(mut self, values: Vec<T>) -> Self {
+        self.preset = Some(values);
+        self
+    }
+
+    /// Resolves the prompt with the [`MultiSelect::on_timeout`] action if no key
+    /// is pressed for the duration, the countdown restarts on every key.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout.duration = Some(timeout);
+        self
+    }
+
+    /// Sets what happens on the [`MultiSelect::timeout`]. Default: [`TimeoutAction::Cancel`].
+    pub fn on_timeout(mut self, action: TimeoutAction) -> Self {
+        self.timeout.action = action;
+        self
+    }
+
+    /// Ignores `Esc`, so the prompt can only be submitted. Default: `false`.
+    pub fn no_cancel(mut self, no_cancel: bool) -> Self {
+        self.no_cancel = no_cancel;
+        self
+    }
+
+    /// Discards the keys typed before the prompt shows up, so they can't
+    /// answer it by accident. Default: `false`, the keys typed ahead are kept.
+    pub fn flush_input(mut self, flush: bool) -> Self {
+        self.flush_input = flush;
+        self
+    }
+
     /// Starts the prompt interaction.
     pub fn interact(&mut self) -> io::Result<Vec<T>> {
-        if let Some(initial_values) = &self.initial_values {
-            for item in self.items.iter_mut() {
-                if initial_values.contains(&item.value) {
-                    item.selected = true;
-                }
+        <Self as PromptInteraction<Vec<T>>>::interact(self)
+    }
+
+    /// Starts the prompt interaction returning a [`PromptError`](crate::PromptError)
+    /// which tells the cancellation apart from the I/O failures.
+    pub fn interact2(&mut self) -> Result<Vec<T>, crate::PromptError> {
+        Ok(self.interact()?)
+    }
+
+    /// Starts the prompt interaction on the given terminal instead of stderr.
+    pub fn interact_on(&mut self, term: &Term) -> io::Result<Vec<T>> {
+        <Self as PromptInteraction<Vec<T>>>::interact_on(self, &mut term.clone())
+    }
+
+    /// Starts the prompt interaction awaiting the keys from the stream.
+    #[cfg(feature = "async")]
+    pub async fn interact_async<S: crate::KeyStream>(
+        &mut self,
+        keys: &mut S,
+    ) -> io::Result<Vec<T>> {
+        <Self as PromptInteraction<Vec<T>>>::interact_async(self, keys).await
+    }
+
+    /// Returns the number of lines the prompt occupies on the terminal of
+    /// the given width, e.g. to reserve the space in a composite UI.
+    pub fn rendered_height(&mut self, width: usize) -> usize {
+        <Self as PromptInteraction<Vec<T>>>::rendered_height(self, &State::Active, width)
+    }
+
+    /// Starts the prompt interaction drawing it to the given writer.
+    ///
+    /// The keys are read from the terminal, see [`MultiSelect::interact`].
+    pub fn interact_with_writer<W: Write>(&mut self, writer: &mut W) -> io::Result<Vec<T>> {
+        <Self as PromptInteraction<Vec<T>>>::interact_with_writer(self, writer)
+    }
+}
+
+impl<T: Default> MultiSelect<T> {
+    /// Returns the indices of the items in the order they are rendered.
+    fn visible_order(&self) -> Vec<usize> {
+        let mut order = (0..self.items.len()).collect::<Vec<_>>();
+        if self.selected_first {
+            order.sort_by_key(|&i| !self.items[i].selected);
+        }
+        order
+    }
+
+    /// Drops the unchecked items from the selection order and appends
+    /// the newly checked ones.
+    fn sync_selection_order(&mut self) {
+        let items = &self.items;
+        self.selection_order.retain(|&i| items[i].selected);
+        for (i, item) in self.items.iter().enumerate() {
+            if item.selected && !self.selection_order.contains(&i) {
+                self.selection_order.push(i);
             }
         }
-        <Self as PromptInteraction<Vec<T>>>::interact(self)
+    }
+
+    /// Returns the indices of the selected items in the returned order.
+    fn selected_indices(&self) -> Vec<usize> {
+        match self.preserve_selection_order {
+            true => self.selection_order.clone(),
+            false => (0..self.items.len())
+                .filter(|&i| self.items[i].selected)
+                .collect(),
+        }
+    }
+
+    fn selected_count(&self) -> usize {
+        self.items.iter().filter(|item| item.selected).count()
+    }
+
+    fn min_error_message(&self) -> String {
+        let min = self.min.unwrap_or_default();
+        match &self.min_error {
+            Some(error) => error.clone(),
+            None => format!("Select at least {min} {}", plural_items(min)),
+        }
+    }
+
+    fn max_error_message(&self) -> String {
+        let max = self.max.unwrap_or_default();
+        match &self.max_error {
+            Some(error) => error.clone(),
+            None => format!("Select at most {max} {}", plural_items(max)),
+        }
+    }
+
+    fn enabled_count(&self) -> usize {
+        self.items.iter().filter(|item| !item.disabled).count()
+    }
+
+    /// Moves the cursor to the first visible item matching the typed prefix.
+    fn jump(&mut self, order: &[usize]) {
+        // The empty labels of the disabled items never match the prefix.
+        let labels = order.iter().map(|&i| match self.items[i].disabled {
+            true => "",
+            false => self.items[i].label.as_str(),
+        });
+        if let Some(position) = self.type_ahead.find(labels) {
+            self.cursor = order[position];
+        }
     }
 }
 
-impl<T: Default + Clone> PromptInteraction<Vec<T>> for MultiSelect<T> {
-    fn on(&mut self, event: &Event) -> State<Vec<T>> {
-        let Event::Key(key) = event;
+impl<T: Default + Clone> MultiSelect<T> {
+    /// Submits the selected values checking the selection limits.
+    fn submit(&self) -> State<Vec<T>> {
+        let selected_items = self
+            .selected_indices()
+            .into_iter()
+            .map(|i| self.items[i].value.clone())
+            .collect::<Vec<_>>();
+
+        if selected_items.is_empty() && self.required {
+            return State::Error("Input required".to_string());
+        }
+        if matches!(self.min, Some(min) if selected_items.len() < min) {
+            return State::Error(self.min_error_message());
+        }
+        if matches!(self.max, Some(max) if selected_items.len() > max) {
+            return State::Error(self.max_error_message());
+        }
 
+        State::Submit(selected_items)
+    }
+
+    fn on_key(&mut self, key: &Key) -> State<Vec<T>> {
         match key {
-            Key::ArrowLeft | Key::ArrowUp => {
-                if self.cursor > 0 {
-                    self.cursor -= 1;
-                }
+            Key::ArrowLeft => self.move_cursor(false),
+            Key::ArrowRight => self.move_cursor(true),
+            Key::Char(chr) if !chr.is_ascii_control() => {
+                self.type_ahead.push(*chr);
+                self.jump(&self.visible_order());
+            }
+            Key::Backspace if self.type_ahead.pop() => {
+                self.jump(&self.visible_order());
+            }
+            _ => {}
+        }
+
+        State::Active
+    }
+
+    /// Handles the resolved actions and keys until the prompt is resolved.
+    fn handle_strokes(&mut self, strokes: Vec<Stroke>) -> State<Vec<T>> {
+        for stroke in strokes {
+            let state = match stroke {
+                Stroke::Action(action) => self.on_action(action),
+                Stroke::Key(key) => self.on_key(&key),
+            };
+            if !matches!(state, State::Active) {
+                return state;
             }
-            Key::ArrowRight | Key::ArrowDown => {
-                if self.cursor < self.items.len() - 1 {
-                    self.cursor += 1;
+        }
+
+        State::Active
+    }
+
+    fn on_action(&mut self, action: Action) -> State<Vec<T>> {
+        match action {
+            Action::MoveUp => self.move_cursor(false),
+            Action::MoveDown => self.move_cursor(true),
+            Action::First | Action::Last => {
+                let order = self.visible_order();
+                let enabled = |&&i: &&usize| !self.items[i].disabled;
+                let target = match action {
+                    Action::First => order.iter().find(enabled),
+                    _ => order.iter().rev().find(enabled),
+                };
+                if let Some(&i) = target {
+                    self.cursor = i;
                 }
             }
-            Key::Char(' ') => {
-                self.items[self.cursor].selected = !self.items[self.cursor].selected;
+            // Disabled items can't be toggled.
+            Action::Toggle if matches!(self.items.get(self.cursor), Some(item) if item.disabled) => {
             }
-            Key::Enter => {
-                let selected_items = self
-                    .items
-                    .iter()
-                    .filter(|item| item.selected)
-                    .map(|item| item.value.clone())
-                    .collect::<Vec<_>>();
+            Action::Toggle if !self.items.is_empty() => {
+                let item = &self.items[self.cursor];
+                if !item.selected && matches!(self.max, Some(max) if self.selected_count() >= max) {
+                    return State::Error(self.max_error_message());
+                }
+                self.items[self.cursor].selected = !item.selected;
+                self.sync_selection_order();
+            }
+            Action::Submit => return self.submit(),
+            Action::Cancel if !self.no_cancel => return State::Cancel,
+            Action::Toggle | Action::Cancel => {}
+        }
+
+        State::Active
+    }
+
+    /// Moves the cursor to the next (or previous) enabled item in the rendered order.
+    fn move_cursor(&mut self, forward: bool) {
+        let order = self.visible_order();
+        let position = order.iter().position(|&i| i == self.cursor).unwrap_or(0);
+
+        let next = match forward {
+            true => order
+                .iter()
+                .skip(position + 1)
+                .find(|&&i| !self.items[i].disabled),
+            false => order[..position]
+                .iter()
+                .rev()
+                .find(|&&i| !self.items[i].disabled),
+        };
+        if let Some(&i) = next {
+            self.cursor = i;
+        }
+    }
+}
+
+impl<T: Default + Clone + Eq> PromptInteraction<Vec<T>> for MultiSelect<T> {
+    fn cancelable(&self) -> bool {
+        !self.no_cancel
+    }
+
+    fn flush_input(&self) -> bool {
+        self.flush_input
+    }
+
+    fn timeout(&self) -> Timeout {
+        self.timeout
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        self.chords.remaining(&keymap_or_global(&self.keymap))
+    }
 
-                if selected_items.is_empty() && self.required {
-                    return State::Error("Input required".to_string());
+    fn on_timeout(&mut self) -> State<Vec<T>> {
+        self.submit()
+    }
+
+    fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    fn init(&mut self) {
+        if let Some(initial_values) = &self.initial_values {
+            for item in self.items.iter_mut() {
+                if !item.disabled && initial_values.contains(&item.value) {
+                    item.selected = true;
+                }
+            }
+        } else if let Some(answer) = stored_answer(&self.prompt_id) {
+            let labels = answer.split(',').map(str::trim).collect::<Vec<_>>();
+            for item in self.items.iter_mut() {
+                if !item.disabled && labels.contains(&item.label.as_str()) {
+                    item.selected = true;
                 }
+            }
+        }
+        self.sync_selection_order();
+        if matches!(self.items.get(self.cursor), Some(item) if item.disabled) {
+            let order = self.visible_order();
+            if let Some(&i) = order.iter().find(|&&i| !self.items[i].disabled) {
+                self.cursor = i;
+            }
+        }
+    }
 
-                return State::Submit(selected_items);
+    fn preset(&mut self) -> Option<State<Vec<T>>> {
+        let values = self.preset.as_ref()?;
+        for item in self.items.iter_mut() {
+            item.selected = !item.disabled && values.contains(&item.value);
+        }
+        self.sync_selection_order();
+        Some(self.on_action(Action::Submit))
+    }
+
+    fn on(&mut self, event: &Event) -> State<Vec<T>> {
+        let Event::Key(key) = event;
+
+        if !self.repeat.accept(key) {
+            return State::Active;
+        }
+
+        if self.toggle_all_key.as_ref() == Some(key) {
+            let enabled = self.enabled_count();
+            let select = self.selected_count() < enabled;
+            if select && matches!(self.max, Some(max) if enabled > max) {
+                return State::Error(self.max_error_message());
             }
-            _ => {}
+            for item in self.items.iter_mut().filter(|item| !item.disabled) {
+                item.selected = select;
+            }
+            self.sync_selection_order();
+            return State::Active;
         }
 
-        State::Active
+        let keymap = keymap_or_global(&self.keymap);
+        let strokes = self.chords.feed(&keymap, key.clone());
+        self.handle_strokes(strokes)
+    }
+
+    fn on_idle(&mut self) -> Option<State<Vec<T>>> {
+        let strokes = self.chords.expire(&keymap_or_global(&self.keymap));
+        match strokes.is_empty() {
+            true => None,
+            false => Some(self.handle_strokes(strokes)),
+        }
     }
 
     fn render(&mut self, state: &State<Vec<T>>) -> String {
         let theme = THEME.lock().unwrap();
 
-        let line1 = theme.format_header(&state.into(), &self.prompt);
+        let line1 = match &self.total {
+            Some(total) => {
+                let sum = self
+                    .items
+                    .iter()
+                    .filter(|item| item.selected)
+                    .map(|item| total(&item.value))
+                    .sum();
+                theme.format_header_inline(&state.into(), &self.prompt)
+                    + &theme.format_multiselect_total(&state.into(), sum)
+                    + "\n"
+            }
+            None => theme.format_header(&state.into(), &self.prompt),
+        };
 
         let mut line2 = String::new();
-        for (i, item) in self.items.iter().enumerate() {
-            line2.push_str(&theme.format_multiselect_item(
-                &state.into(),
-                item.selected,
-                i == self.cursor,
-                &item.label,
-                &item.hint,
-            ));
+        let summary = self.summary_separator.is_some() || self.summary_max.is_some();
+        if let (State::Submit(_), true) = (state, summary) {
+            let separator = self.summary_separator.as_deref().unwrap_or(", ");
+            let labels = self
+                .selected_indices()
+                .into_iter()
+                .map(|i| self.items[i].label.as_str())
+                .collect::<Vec<_>>();
+
+            let shown = self.summary_max.unwrap_or(usize::MAX).min(labels.len());
+            let mut summary = labels[..shown].join(separator);
+            if shown < labels.len() {
+                if shown > 0 {
+                    summary.push_str(separator);
+                }
+                let more = labels.len() - shown;
+                summary.push_str(&format!("{} (+{more} more)", theme.ellipsis()));
+            }
+
+            line2.push_str(&theme.format_multiselect_summary(&state.into(), &summary));
+        } else {
+            for i in self.visible_order() {
+                let item = &self.items[i];
+                if item.disabled {
+                    line2.push_str(&theme.format_disabled_item(
+                        &state.into(),
+                        &item.label,
+                        &item.hint,
+                    ));
+                    continue;
+                }
+                line2.push_str(&theme.format_multiselect_item(
+                    &state.into(),
+                    item.selected,
+                    i == self.cursor,
+                    &item.label,
+                    &item.hint,
+                ));
+            }
         }
         let line3 = theme.format_footer(&state.into());
 
         line1 + &line2 + &line3
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(multiselect: &mut MultiSelect<&'static str>) -> Vec<String> {
+        let frame = multiselect.render(&State::Active);
+        console::strip_ansi_codes(&frame)
+            .lines()
+            .skip(1)
+            .filter_map(|line| line.split_whitespace().last().map(str::to_string))
+            .filter(|label| label.starts_with(char::is_alphabetic))
+            .collect()
+    }
+
+    fn press(
+        multiselect: &mut MultiSelect<&'static str>,
+        keys: &[Key],
+    ) -> State<Vec<&'static str>> {
+        let mut state = State::Active;
+        for key in keys {
+            state = multiselect.handle_key(key.clone());
+        }
+        state
+    }
+
+    #[test]
+    fn selected_first_moves_the_checked_items_to_the_top() {
+        let mut multiselect = MultiSelect::new("Pick")
+            .item("a", "Alpha", "")
+            .item("b", "Bravo", "")
+            .item("c", "Charlie", "")
+            .item("d", "Delta", "")
+            .selected_first(true);
+        multiselect.init();
+
+        // Checks Delta, then Bravo which is moved between Delta and Alpha.
+        press(&mut multiselect, &[Key::End, Key::Char(' ')]);
+        assert_eq!(
+            labels(&mut multiselect),
+            ["Delta", "Alpha", "Bravo", "Charlie"]
+        );
+        press(
+            &mut multiselect,
+            &[Key::ArrowDown, Key::ArrowDown, Key::Char(' ')],
+        );
+        assert_eq!(
+            labels(&mut multiselect),
+            ["Bravo", "Delta", "Alpha", "Charlie"]
+        );
+
+        let state = press(&mut multiselect, &[Key::Enter]);
+        assert_eq!(state.submitted(), Some(vec!["b", "d"]));
+    }
+
+    #[test]
+    fn the_order_is_kept_without_selected_first() {
+        let mut multiselect = MultiSelect::new("Pick")
+            .item("a", "Alpha", "")
+            .item("b", "Bravo", "")
+            .item("c", "Charlie", "");
+        multiselect.init();
+
+        press(&mut multiselect, &[Key::End, Key::Char(' ')]);
+        assert_eq!(labels(&mut multiselect), ["Alpha", "Bravo", "Charlie"]);
+    }
+
+    #[test]
+    fn summary_joins_and_truncates_the_submitted_items() {
+        let summary = |multiselect: MultiSelect<&'static str>| {
+            let mut multiselect = multiselect
+                .item("a", "Alpha", "")
+                .item("b", "Bravo", "")
+                .item("c", "Charlie", "")
+                .item("d", "Delta", "")
+                .item("e", "Echo", "")
+                .initial_values(vec!["a", "b", "c", "d", "e"]);
+            multiselect.init();
+            let state = press(&mut multiselect, &[Key::Enter]);
+            console::strip_ansi_codes(&multiselect.render(&state)).into_owned()
+        };
+
+        let frame = summary(MultiSelect::new("Pick").summary_separator(" | "));
+        assert!(
+            frame.contains("Alpha | Bravo | Charlie | Delta | Echo"),
+            "{frame}"
+        );
+
+        let ellipsis = THEME.lock().unwrap().ellipsis();
+        let frame = summary(MultiSelect::new("Pick").summary_max(2));
+        assert!(
+            frame.contains(&format!("Alpha, Bravo, {ellipsis} (+3 more)")),
+            "{frame}"
+        );
+        assert!(!frame.contains("Charlie"));
+
+        let frame = summary(
+            MultiSelect::new("Pick")
+                .summary_separator(" / ")
+                .summary_max(5),
+        );
+        assert!(frame.contains("Alpha / Bravo / Charlie / Delta / Echo"));
+        assert!(!frame.contains("more"));
+    }
+
+    #[test]
+    fn running_total_follows_the_selection() {
+        let mut multiselect = MultiSelect::new("Files")
+            .item(120, "photo.jpg", "")
+            .item(40, "notes.txt", "")
+            .item(8, "todo.md", "")
+            .running_total(|size| *size);
+        multiselect.init();
+        let header = |multiselect: &mut MultiSelect<i64>| {
+            let frame = multiselect.render(&State::Active);
+            console::strip_ansi_codes(frame.lines().next().unwrap()).into_owned()
+        };
+
+        assert!(header(&mut multiselect).ends_with("Files (total: 0)"));
+        multiselect.handle_key(Key::Char(' '));
+        assert!(header(&mut multiselect).ends_with("(total: 120)"));
+        multiselect.handle_key(Key::ArrowDown);
+        multiselect.handle_key(Key::ArrowDown);
+        multiselect.handle_key(Key::Char(' '));
+        assert!(header(&mut multiselect).ends_with("(total: 128)"));
+        multiselect.handle_key(Key::ArrowUp);
+        multiselect.handle_key(Key::ArrowUp);
+        multiselect.handle_key(Key::Char(' '));
+        assert!(header(&mut multiselect).ends_with("(total: 8)"));
+    }
+
+    #[test]
+    fn preserve_selection_order_returns_the_toggle_order() {
+        let multiselect = || {
+            let mut multiselect = MultiSelect::new("Pick")
+                .item("a", "Alpha", "")
+                .item("b", "Bravo", "")
+                .item("c", "Charlie", "")
+                .preserve_selection_order(true);
+            multiselect.init();
+            // Checks Charlie, Alpha and Bravo in this order.
+            let keys = [Key::End, Key::Char(' '), Key::Home, Key::Char(' ')];
+            press(&mut multiselect, &keys);
+            press(&mut multiselect, &[Key::ArrowDown, Key::Char(' ')]);
+            multiselect
+        };
+
+        let state = press(&mut multiselect(), &[Key::Enter]);
+        assert_eq!(state.submitted(), Some(vec!["c", "a", "b"]));
+
+        // Unchecking and checking Charlie again moves it to the end.
+        let keys = [Key::End, Key::Char(' '), Key::Char(' '), Key::Enter];
+        let state = press(&mut multiselect(), &keys);
+        assert_eq!(state.submitted(), Some(vec!["a", "b", "c"]));
+
+        // The default keeps the order of the items.
+        let mut unordered = multiselect().preserve_selection_order(false);
+        let state = press(&mut unordered, &[Key::Enter]);
+        assert_eq!(state.submitted(), Some(vec!["a", "b", "c"]));
+
+        let keys = [Key::Home, Key::Char(' '), Key::Char(' '), Key::Enter];
+        let state = press(&mut multiselect(), &keys);
+        assert_eq!(state.submitted(), Some(vec!["c", "b", "a"]));
+    }
+
+    fn nato() -> MultiSelect<&'static str> {
+        MultiSelect::new("Pick")
+            .item("a", "Alpha", "")
+            .item("b", "Bravo", "")
+            .item("c", "Charlie", "")
+            .item("d", "Delta", "")
+    }
+
+    fn error(state: State<Vec<&'static str>>) -> Option<String> {
+        match state {
+            State::Error(err) => Some(err),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn toggle_beyond_max_is_blocked_with_the_error() {
+        let mut multiselect = nato().max(2);
+        multiselect.init();
+
+        let state = press(
+            &mut multiselect,
+            &[
+                Key::Char(' '),
+                Key::ArrowDown,
+                Key::Char(' '),
+                Key::ArrowDown,
+            ],
+        );
+        assert!(matches!(state, State::Active));
+        assert_eq!(
+            error(press(&mut multiselect, &[Key::Char(' ')])).as_deref(),
+            Some("Select at most 2 items")
+        );
+        assert!(!multiselect.items[2].selected, "the toggle is blocked");
+
+        let state = press(&mut multiselect, &[Key::ArrowUp, Key::Char(' ')]);
+        assert!(matches!(state, State::Active), "unchecking is allowed");
+        assert_eq!(
+            press(&mut multiselect, &[Key::Enter]).submitted(),
+            Some(vec!["a"])
+        );
+    }
+
+    #[test]
+    fn fewer_items_than_min_show_the_error_on_enter() {
+        let mut multiselect = nato().min(2);
+        multiselect.init();
+
+        assert_eq!(
+            error(press(&mut multiselect, &[Key::Char(' '), Key::Enter])).as_deref(),
+            Some("Select at least 2 items")
+        );
+        let state = press(
+            &mut multiselect,
+            &[Key::ArrowDown, Key::Char(' '), Key::Enter],
+        );
+        assert_eq!(state.submitted(), Some(vec!["a", "b"]));
+    }
+
+    #[test]
+    fn min_and_max_errors_can_be_overridden() {
+        let mut multiselect = nato()
+            .required(false)
+            .min(1)
+            .min_error("Pick a letter")
+            .max(1)
+            .max_error("Only one letter");
+        multiselect.init();
+
+        assert_eq!(
+            error(press(&mut multiselect, &[Key::Enter])).as_deref(),
+            Some("Pick a letter")
+        );
+        let state = press(
+            &mut multiselect,
+            &[Key::Char(' '), Key::ArrowDown, Key::Char(' ')],
+        );
+        assert_eq!(error(state).as_deref(), Some("Only one letter"));
+    }
+
+    #[test]
+    fn toggle_all_beyond_max_is_blocked_with_the_error() {
+        let mut multiselect = nato().max(3).toggle_all_key(Key::Char('*'));
+        multiselect.init();
+
+        assert_eq!(
+            error(press(&mut multiselect, &[Key::Char('*')])).as_deref(),
+            Some("Select at most 3 items")
+        );
+        assert_eq!(multiselect.selected_count(), 0, "nothing is selected");
+
+        let mut multiselect = MultiSelect::new("Pick")
+            .item("a", "Alpha", "")
+            .item("b", "Bravo", "")
+            .item_disabled("c", "Charlie", "")
+            .item("d", "Delta", "")
+            .max(3)
+            .toggle_all_key(Key::Char('*'));
+        multiselect.init();
+
+        assert!(matches!(
+            press(&mut multiselect, &[Key::Char('*')]),
+            State::Active
+        ));
+        assert_eq!(
+            press(&mut multiselect, &[Key::Enter]).submitted(),
+            Some(vec!["a", "b", "d"]),
+            "the disabled item doesn't count against the max"
+        );
+    }
+
+    #[test]
+    fn space_on_a_disabled_item_does_nothing() {
+        let mut multiselect = MultiSelect::new("Pick")
+            .item_disabled("a", "Alpha", "")
+            .item_disabled("b", "Bravo", "")
+            .required(false);
+        multiselect.init();
+        assert_eq!(multiselect.cursor, 0, "there is no enabled item to move to");
+
+        assert!(matches!(
+            press(&mut multiselect, &[Key::Char(' ')]),
+            State::Active
+        ));
+        assert!(!multiselect.items[0].selected);
+        assert_eq!(
+            press(&mut multiselect, &[Key::Enter]).submitted(),
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn type_ahead_skips_the_disabled_labels() {
+        let mut multiselect = MultiSelect::new("Pick")
+            .item("a", "Alpha", "")
+            .item_disabled("b", "Beta", "")
+            .item("c", "Bravo", "");
+        multiselect.init();
+
+        press(&mut multiselect, &[Key::Char('b')]);
+        assert_eq!(multiselect.cursor, 2);
+        press(&mut multiselect, &[Key::Char(' ')]);
+        assert_eq!(
+            press(&mut multiselect, &[Key::Enter]).submitted(),
+            Some(vec!["c"])
+        );
+    }
+}
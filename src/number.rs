@@ -0,0 +1,503 @@
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+use console::{Key, Term};
+
+use crate::{
+    input::is_number_prefix,
+    prompt::{
+        cursor::StringCursor,
+        interaction::{fit_input, Event, PromptInteraction, State, Timeout, TimeoutAction},
+    },
+    state::stored_answer,
+    theme::THEME,
+};
+
+type StepCallback<T> = Box<dyn Fn(&T) -> Option<T>>;
+
+/// A number type the [`Number::step`] arrows can change without overflowing.
+///
+/// It's implemented for the primitive integer and floating point types.
+pub trait NumberStep: Sized {
+    /// Returns the sum, `None` if it overflows the type.
+    fn checked_step_up(&self, step: &Self) -> Option<Self>;
+
+    /// Returns the difference, `None` if it overflows the type.
+    fn checked_step_down(&self, step: &Self) -> Option<Self>;
+}
+
+macro_rules! impl_integer_step {
+    ($($ty:ty),*) => {$(
+        impl NumberStep for $ty {
+            fn checked_step_up(&self, step: &Self) -> Option<Self> {
+                self.checked_add(*step)
+            }
+
+            fn checked_step_down(&self, step: &Self) -> Option<Self> {
+                self.checked_sub(*step)
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_float_step {
+    ($($ty:ty),*) => {$(
+        impl NumberStep for $ty {
+            fn checked_step_up(&self, step: &Self) -> Option<Self> {
+                Some(self + step).filter(|value| value.is_finite())
+            }
+
+            fn checked_step_down(&self, step: &Self) -> Option<Self> {
+                Some(self - step).filter(|value| value.is_finite())
+            }
+        }
+    )*};
+}
+
+impl_integer_step!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_float_step!(f32, f64);
+
+/// A prompt that accepts a number, rejecting the other characters while
+/// typing, and checks the range of the value on submit.
+///
+/// # Example
+///
+/// ```
+/// use cliclack::Number;
+///
+/// # fn test() -> std::io::Result<()> {
+/// let port: u16 = Number::new("Port")
+///     .default_value(8080)
+///     .min(1024)
+///     .step(1)
+///     .interact()?;
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
+pub struct Number<T> {
+    prompt: String,
+    input: StringCursor,
+    placeholder: StringCursor,
+    default: Option<T>,
+    prompt_id: Option<String>,
+    min: Option<T>,
+    max: Option<T>,
+    increment: Option<StepCallback<T>>,
+    decrement: Option<StepCallback<T>>,
+    no_cancel: bool,
+    flush_input: bool,
+    timeout: Timeout,
+    help: Option<String>,
+    preset: Option<T>,
+}
+
+impl<T> Number<T>
+where
+    T: FromStr + PartialOrd + Display + Clone,
+{
+    /// Creates a new number prompt.
+    pub fn new(prompt: impl Display) -> Self {
+        Self {
+            prompt: prompt.to_string(),
+            input: StringCursor::default(),
+            placeholder: StringCursor::default(),
+            default: None,
+            prompt_id: None,
+            min: None,
+            max: None,
+            increment: None,
+            decrement: None,
+            no_cancel: false,
+            flush_input: false,
+            timeout: Timeout::default(),
+            help: None,
+            preset: None,
+        }
+    }
+
+    /// Sets the placeholder (hint) text for the input.
+    pub fn placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder.extend(placeholder);
+        self
+    }
+
+    /// Sets the value submitted if nothing is typed, it's shown dimmed
+    /// unless the placeholder is set.
+    pub fn default_value(mut self, value: T) -> Self {
+        self.default = Some(value);
+        self
+    }
+
+    /// Sets the identifier of the prompt to look up a default value
+    /// with the global [`StateProvider`](crate::StateProvider).
+    ///
+    /// The stored answer is used only if [`Number::default_value`] is not set
+    /// and the answer is a number.
+    pub fn prompt_id(mut self, id: &str) -> Self {
+        self.prompt_id = Some(id.into());
+        self
+    }
+
+    /// Sets the minimum value, a smaller one is rejected on submit.
+    pub fn min(mut self, min: T) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Sets the maximum value, a greater one is rejected on submit.
+    pub fn max(mut self, max: T) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Sets the help text which is shown in a box above the prompt on `?`.
+    pub fn help(mut self, help: impl Display) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+
+    /// Submits the number without the interaction, e.g. for a dry run,
+    /// drawing only the submitted prompt.
+    ///
+    /// An out-of-range number is returned as [`io::ErrorKind::InvalidInput`] error.
+    pub fn preset_answer(mut self, answer: T) -> Self {
+        self.preset = Some(answer);
+        self
+    }
+
+    /// Resolves the prompt with the [`Number::on_timeout`] action if no key
+    /// is pressed for the duration, the countdown restarts on every key.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout.duration = Some(timeout);
+        self
+    }
+
+    /// Sets what happens on the [`Number::timeout`]. Default: [`TimeoutAction::Cancel`].
+    pub fn on_timeout(mut self, action: TimeoutAction) -> Self {
+        self.timeout.action = action;
+        self
+    }
+
+    /// Ignores `Esc`, so the prompt can only be submitted. Default: `false`.
+    pub fn no_cancel(mut self, no_cancel: bool) -> Self {
+        self.no_cancel = no_cancel;
+        self
+    }
+
+    /// Discards the keys typed before the prompt shows up, so they can't
+    /// answer it by accident. Default: `false`, the keys typed ahead are kept.
+    pub fn flush_input(mut self, flush: bool) -> Self {
+        self.flush_input = flush;
+        self
+    }
+
+    /// Starts the prompt interaction.
+    pub fn interact(&mut self) -> io::Result<T> {
+        <Self as PromptInteraction<T>>::interact(self)
+    }
+
+    /// Starts the prompt interaction returning a [`PromptError`](crate::PromptError)
+    /// which tells the cancellation apart from the I/O failures.
+    pub fn interact2(&mut self) -> Result<T, crate::PromptError> {
+        Ok(self.interact()?)
+    }
+
+    /// Starts the prompt interaction on the given terminal instead of stderr.
+    pub fn interact_on(&mut self, term: &Term) -> io::Result<T> {
+        <Self as PromptInteraction<T>>::interact_on(self, &mut term.clone())
+    }
+
+    /// Starts the prompt interaction awaiting the keys from the stream.
+    #[cfg(feature = "async")]
+    pub async fn interact_async<S: crate::KeyStream>(&mut self, keys: &mut S) -> io::Result<T> {
+        <Self as PromptInteraction<T>>::interact_async(self, keys).await
+    }
+
+    /// Returns the number of lines the prompt occupies on the terminal of
+    /// the given width, e.g. to reserve the space in a composite UI.
+    pub fn rendered_height(&mut self, width: usize) -> usize {
+        <Self as PromptInteraction<T>>::rendered_height(self, &State::Active, width)
+    }
+
+    /// Starts the prompt interaction drawing it to the given writer.
+    ///
+    /// The keys are read from the terminal, see [`Number::interact`].
+    pub fn interact_with_writer<W: Write>(&mut self, writer: &mut W) -> io::Result<T> {
+        <Self as PromptInteraction<T>>::interact_with_writer(self, writer)
+    }
+
+    /// Returns the typed value, or the default one if nothing is typed.
+    fn value(&self) -> Option<Result<T, T::Err>> {
+        match &self.default {
+            Some(default) if self.input.is_empty() => Some(Ok(default.clone())),
+            _ if self.input.is_empty() => None,
+            _ => Some(self.input.to_string().parse()),
+        }
+    }
+
+    /// Changes the value by the step, keeping it within the range.
+    fn change(&mut self, up: bool) {
+        let value = match self.value() {
+            Some(Ok(value)) => value,
+            Some(Err(_)) => return,
+            None => match (&self.min, &self.max) {
+                (Some(bound), _) | (None, Some(bound)) => {
+                    let bound = bound.clone();
+                    self.input.replace(&bound.to_string());
+                    return;
+                }
+                (None, None) => return,
+            },
+        };
+
+        let (step, limit) = match up {
+            true => (&self.increment, &self.max),
+            false => (&self.decrement, &self.min),
+        };
+        let Some(step) = step else {
+            return;
+        };
+
+        // Overflowing the type goes beyond the limit too, without one
+        // the value is kept.
+        let value = match (limit, step(&value)) {
+            (Some(limit), _) if (up && value >= *limit) || (!up && value <= *limit) => {
+                limit.clone()
+            }
+            (Some(limit), Some(next)) if (up && next > *limit) || (!up && next < *limit) => {
+                limit.clone()
+            }
+            (_, Some(next)) => next,
+            (Some(limit), None) => limit.clone(),
+            (None, None) => value,
+        };
+        self.input.replace(&value.to_string());
+    }
+}
+
+impl<T> Number<T>
+where
+    T: FromStr + PartialOrd + Display + Clone + NumberStep + 'static,
+{
+    /// Makes `Up` and `Down` arrows increment and decrement the value by the
+    /// step, within [`Number::min`] and [`Number::max`]. Default: none,
+    /// the arrows are ignored.
+    ///
+    /// A step overflowing the type is ignored, e.g. `Up` at `255` for `u8`.
+    pub fn step(mut self, step: T) -> Self {
+        let down = step.clone();
+        self.increment = Some(Box::new(move |value: &T| value.checked_step_up(&step)));
+        self.decrement = Some(Box::new(move |value: &T| value.checked_step_down(&down)));
+        self
+    }
+}
+
+impl<T> PromptInteraction<T> for Number<T>
+where
+    T: FromStr + PartialOrd + Display + Clone,
+{
+    fn cancelable(&self) -> bool {
+        !self.no_cancel
+    }
+
+    fn flush_input(&self) -> bool {
+        self.flush_input
+    }
+
+    fn timeout(&self) -> Timeout {
+        self.timeout
+    }
+
+    fn on_timeout(&mut self) -> State<T> {
+        match &self.default {
+            Some(default) => State::Submit(default.clone()),
+            None => State::Cancel,
+        }
+    }
+
+    fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    fn init(&mut self) {
+        if self.default.is_none() {
+            self.default = stored_answer(&self.prompt_id).and_then(|answer| answer.parse().ok());
+        }
+    }
+
+    fn preset(&mut self) -> Option<State<T>> {
+        let answer = self.preset.as_ref()?.to_string();
+        self.input.replace(&answer);
+        Some(self.on(&Event::Key(Key::Enter)))
+    }
+
+    fn input(&mut self) -> Option<&mut StringCursor> {
+        Some(&mut self.input)
+    }
+
+    fn accept_char(&self, chr: char) -> bool {
+        is_number_prefix(&self.input.with_inserted(chr))
+    }
+
+    fn on(&mut self, event: &Event) -> State<T> {
+        let Event::Key(key) = event;
+
+        match key {
+            Key::ArrowUp => self.change(true),
+            Key::ArrowDown => self.change(false),
+            Key::Enter => {
+                let value = match self.value() {
+                    Some(Ok(value)) => value,
+                    Some(Err(_)) => return State::Error("Invalid number".to_string()),
+                    None => return State::Error("Input required".to_string()),
+                };
+                if let Some(min) = self.min.as_ref().filter(|min| value < **min) {
+                    return State::Error(format!("Must be at least {min}"));
+                }
+                if let Some(max) = self.max.as_ref().filter(|max| value > **max) {
+                    return State::Error(format!("Must be at most {max}"));
+                }
+                return State::Submit(value);
+            }
+            _ => {}
+        }
+
+        State::Active
+    }
+
+    fn render(&mut self, state: &State<T>) -> String {
+        let theme = THEME.lock().unwrap();
+
+        let line1 = theme.format_header(&state.into(), &self.prompt);
+        let line2 = match state {
+            State::Submit(value) => {
+                let mut submitted = StringCursor::default();
+                submitted.extend(&value.to_string());
+                theme.format_input(&state.into(), &submitted)
+            }
+            _ if !self.input.is_empty() => {
+                let input = fit_input(state, &self.input, &theme.ellipsis());
+                theme.format_input(&state.into(), &input)
+            }
+            _ => match &self.default {
+                Some(default) if self.placeholder.is_empty() => {
+                    let mut cursor = StringCursor::default();
+                    cursor.extend(&default.to_string());
+                    theme.format_default_value(&state.into(), &cursor)
+                }
+                _ => theme.format_placeholder(&state.into(), &self.placeholder),
+            },
+        };
+        let line3 = theme.format_footer(&state.into());
+
+        line1 + &line2 + &line3
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press<T>(number: &mut Number<T>, keys: &[Key]) -> String
+    where
+        T: FromStr + PartialOrd + Display + Clone,
+    {
+        for key in keys {
+            number.handle_key(key.clone());
+        }
+        number.input.to_string()
+    }
+
+    #[test]
+    fn step_stops_at_the_bounds_of_the_type() {
+        let mut number = Number::<u8>::new("Level").default_value(254).step(1);
+        number.init();
+        assert_eq!(press(&mut number, &[Key::ArrowUp]), "255");
+        assert_eq!(press(&mut number, &[Key::ArrowUp, Key::ArrowUp]), "255");
+
+        let mut number = Number::<u8>::new("Level").default_value(1).step(2);
+        number.init();
+        assert_eq!(
+            press(&mut number, &[Key::ArrowDown]),
+            "1",
+            "0 - 1 underflows"
+        );
+
+        let mut number = Number::<i8>::new("Offset").default_value(-127).step(1);
+        number.init();
+        assert_eq!(
+            press(&mut number, &[Key::ArrowDown, Key::ArrowDown]),
+            "-128"
+        );
+    }
+
+    #[test]
+    fn step_is_clamped_to_the_range() {
+        let mut number = Number::<u16>::new("Port")
+            .default_value(20)
+            .min(0)
+            .max(25)
+            .step(10);
+        number.init();
+        assert_eq!(press(&mut number, &[Key::ArrowUp]), "25");
+        assert_eq!(press(&mut number, &[Key::ArrowUp]), "25");
+        assert_eq!(press(&mut number, &[Key::ArrowDown, Key::ArrowDown]), "5");
+        assert_eq!(press(&mut number, &[Key::ArrowDown]), "0");
+        assert_eq!(press(&mut number, &[Key::ArrowDown]), "0");
+    }
+
+    #[test]
+    fn step_starts_from_the_bound_without_a_value() {
+        let mut number = Number::<i32>::new("Count").min(3).max(9).step(1);
+        number.init();
+        assert_eq!(press(&mut number, &[Key::ArrowUp]), "3");
+
+        let mut number = Number::<f64>::new("Ratio").default_value(0.5).step(0.25);
+        number.init();
+        assert_eq!(press(&mut number, &[Key::ArrowUp, Key::ArrowUp]), "1");
+    }
+
+    #[test]
+    fn arrows_are_ignored_without_a_step() {
+        let mut number = Number::<u8>::new("Level").default_value(7);
+        number.init();
+        assert_eq!(press(&mut number, &[Key::ArrowUp, Key::ArrowDown]), "");
+        assert_eq!(number.handle_key(Key::Enter).submitted(), Some(7));
+    }
+
+    #[test]
+    fn preset_answer_out_of_range_is_an_error() {
+        let mut out = Vec::new();
+        let port = Number::<u16>::new("Port")
+            .min(1024)
+            .preset_answer(8080)
+            .interact_with_writer(&mut out);
+        assert_eq!(port.unwrap(), 8080);
+
+        let err = Number::<u16>::new("Port")
+            .min(1024)
+            .preset_answer(80)
+            .interact_with_writer(&mut out)
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(err.to_string(), "Must be at least 1024");
+    }
+
+    #[test]
+    fn accepted_timeout_submits_only_the_default_value() {
+        let mut number = Number::<u16>::new("Port")
+            .default_value(8080)
+            .on_timeout(TimeoutAction::AcceptDefault);
+        number.init();
+        press(&mut number, &[Key::Char('8'), Key::Char('0')]);
+        assert_eq!(number.timed_out().submitted(), Some(8080));
+
+        let mut number = Number::<u16>::new("Port").on_timeout(TimeoutAction::AcceptDefault);
+        number.init();
+        press(&mut number, &[Key::Char('8'), Key::Char('0')]);
+        assert!(matches!(number.timed_out(), State::Cancel));
+    }
+}
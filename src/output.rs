@@ -0,0 +1,217 @@
+use std::io;
+use std::sync::Mutex;
+
+use console::Term;
+use indicatif::ProgressDrawTarget;
+use once_cell::sync::Lazy;
+
+/// The global output terminal, stderr if not set.
+///
+/// It can be set with [`set_output`] function.
+static OUTPUT: Lazy<Mutex<Option<Term>>> = Lazy::new(|| Mutex::new(None));
+
+/// Sets the global output terminal, which all prompts, spinners, progress
+/// bars and log messages are drawn to. Default: stderr.
+///
+/// ```
+/// use console::Term;
+///
+/// # fn test() -> std::io::Result<()> {
+/// cliclack::set_output(Term::stdout());
+/// let name: String = cliclack::input("Name").interact()?;
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
+///
+/// A [`Term::read_write_pair`] terminal captures the output, e.g. in tests.
+///
+/// See [`reset_output`] for returning to stderr.
+pub fn set_output(term: Term) {
+    *OUTPUT.lock().unwrap() = Some(term);
+}
+
+/// Resets the global output terminal to stderr.
+pub fn reset_output() {
+    *OUTPUT.lock().unwrap() = None;
+}
+
+/// Returns the terminal the output is drawn to.
+pub(crate) fn term() -> Term {
+    OUTPUT.lock().unwrap().clone().unwrap_or_else(Term::stderr)
+}
+
+/// Returns the draw target of the spinners and progress bars, refreshed
+/// at the indicatif default rate.
+pub(crate) fn draw_target() -> ProgressDrawTarget {
+    ProgressDrawTarget::term(term(), 20)
+}
+
+/// Hides the cursor of the terminal until it's dropped, e.g. while
+/// unwinding a panic, or shown explicitly.
+pub(crate) struct HiddenCursor {
+    term: Term,
+    shown: bool,
+}
+
+impl HiddenCursor {
+    /// Hides the cursor of the given terminal.
+    pub(crate) fn new(term: &Term) -> io::Result<Self> {
+        term.hide_cursor()?;
+        Ok(Self {
+            term: term.clone(),
+            shown: false,
+        })
+    }
+
+    /// Shows the cursor again returning the error of the terminal.
+    pub(crate) fn show(mut self) -> io::Result<()> {
+        self.shown = true;
+        self.term.show_cursor()
+    }
+}
+
+impl Drop for HiddenCursor {
+    fn drop(&mut self) {
+        if !self.shown {
+            self.term.show_cursor().ok();
+        }
+    }
+}
+
+/// Serializes the tests which draw to the global output terminal.
+#[cfg(all(test, unix))]
+static CAPTURE_LOCK: Mutex<()> = Mutex::new(());
+
+/// An in-memory terminal writer for the tests.
+#[cfg(all(test, unix))]
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Capture(std::sync::Arc<Mutex<Vec<u8>>>);
+
+#[cfg(all(test, unix))]
+impl Capture {
+    /// Returns a terminal writing to the capture, it's not a tty.
+    pub(crate) fn term(&self) -> Term {
+        Term::read_write_pair(self.clone(), self.clone())
+    }
+
+    /// Returns the captured output.
+    pub(crate) fn text(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+    }
+}
+
+#[cfg(all(test, unix))]
+impl io::Read for Capture {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Ok(0)
+    }
+}
+
+#[cfg(all(test, unix))]
+impl io::Write for Capture {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(all(test, unix))]
+impl std::os::fd::AsRawFd for Capture {
+    fn as_raw_fd(&self) -> std::os::fd::RawFd {
+        -1
+    }
+}
+
+/// Runs the closure with the global output captured, returns its result
+/// and the output.
+#[cfg(all(test, unix))]
+pub(crate) fn capture<R>(f: impl FnOnce() -> R) -> (R, String) {
+    let _lock = CAPTURE_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    let capture = Capture::default();
+    set_output(capture.term());
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+    reset_output();
+    match result {
+        Ok(result) => (result, capture.text()),
+        Err(panic) => std::panic::resume_unwind(panic),
+    }
+}
+
+/// An in-memory draw target of the spinners and progress bars for the tests,
+/// the cursor moves are recorded as `<up N>` and `<clear>` markers.
+#[cfg(test)]
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Recorder(std::sync::Arc<Mutex<String>>);
+
+#[cfg(test)]
+impl Recorder {
+    /// Returns a draw target recording to the recorder.
+    pub(crate) fn target(&self) -> ProgressDrawTarget {
+        ProgressDrawTarget::term_like(Box::new(self.clone()))
+    }
+
+    /// Returns the recorded drawing.
+    pub(crate) fn text(&self) -> String {
+        self.0.lock().unwrap().clone()
+    }
+
+    /// Returns the drawn lines without the styles and the markers.
+    pub(crate) fn lines(&self) -> Vec<String> {
+        let mut text = console::strip_ansi_codes(&self.text()).into_owned();
+        while let Some(start) = text.find('<') {
+            let end = text[start..]
+                .find('>')
+                .map_or(text.len(), |end| start + end + 1);
+            text.replace_range(start..end, "");
+        }
+        text.lines().map(str::to_string).collect()
+    }
+
+    fn record(&self, text: &str) -> io::Result<()> {
+        self.0.lock().unwrap().push_str(text);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl indicatif::TermLike for Recorder {
+    fn width(&self) -> u16 {
+        80
+    }
+
+    fn move_cursor_up(&self, n: usize) -> io::Result<()> {
+        self.record(&format!("<up {n}>"))
+    }
+
+    fn move_cursor_down(&self, n: usize) -> io::Result<()> {
+        self.record(&format!("<down {n}>"))
+    }
+
+    fn move_cursor_right(&self, _n: usize) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn move_cursor_left(&self, _n: usize) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_line(&self, s: &str) -> io::Result<()> {
+        self.record(&format!("{s}\n"))
+    }
+
+    fn write_str(&self, s: &str) -> io::Result<()> {
+        self.record(s)
+    }
+
+    fn clear_line(&self) -> io::Result<()> {
+        self.record("<clear>")
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        Ok(())
+    }
+}
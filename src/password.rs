@@ -1,18 +1,61 @@
 use std::fmt::Display;
-use std::io;
+use std::io::{self, Write};
+use std::time::Duration;
 
-use console::Key;
+use console::{Key, Term};
 
 use crate::{
     prompt::{
         cursor::StringCursor,
-        interaction::{Event, PromptInteraction, State},
+        interaction::{fit_input, Event, PromptInteraction, State, Timeout, TimeoutAction},
     },
     theme::THEME,
     validate::Validate,
 };
 
 type ValidationCallback = Box<dyn Fn(&String) -> Result<(), String>>;
+type GeneratorCallback = Box<dyn Fn() -> String>;
+type StrengthCallback = Box<dyn Fn(&str) -> PasswordStrength>;
+
+/// The strength of the password shown by the [`Password::strength`] meter.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PasswordStrength {
+    /// Easy to guess.
+    Weak,
+    /// Acceptable.
+    Medium,
+    /// Hard to guess.
+    Strong,
+}
+
+impl PasswordStrength {
+    /// The default scoring by the length and the variety of characters:
+    /// lowercase, uppercase, digits, and the others.
+    ///
+    /// * `Strong`: at least 12 characters of 3 kinds.
+    /// * `Medium`: at least 8 characters of 2 kinds.
+    /// * `Weak`: otherwise.
+    pub fn score(password: &str) -> Self {
+        let kinds = [
+            password.chars().any(|chr| chr.is_lowercase()),
+            password.chars().any(|chr| chr.is_uppercase()),
+            password.chars().any(|chr| chr.is_ascii_digit()),
+            password.chars().any(|chr| !chr.is_alphanumeric()),
+        ]
+        .into_iter()
+        .filter(|&kind| kind)
+        .count();
+        let len = password.chars().count();
+
+        if len >= 12 && kinds >= 3 {
+            PasswordStrength::Strong
+        } else if len >= 8 && kinds >= 2 {
+            PasswordStrength::Medium
+        } else {
+            PasswordStrength::Weak
+        }
+    }
+}
 
 /// A prompt that masks the input.
 #[derive(Default)]
@@ -21,6 +64,20 @@ pub struct Password {
     mask: char,
     input: StringCursor,
     validate: Option<ValidationCallback>,
+    generator: Option<GeneratorCallback>,
+    no_cancel: bool,
+    flush_input: bool,
+    timeout: Timeout,
+    submit_key: Option<Key>,
+    confirmation: Option<(String, String)>,
+    confirm_input: StringCursor,
+    confirming: bool,
+    strength: Option<StrengthCallback>,
+    current_strength: Option<PasswordStrength>,
+    reveal: bool,
+    reveal_key: Option<Key>,
+    help: Option<String>,
+    preset: Option<String>,
 }
 
 impl Password {
@@ -39,6 +96,22 @@ impl Password {
         self
     }
 
+    /// Changes the mask character after the construction, e.g. once the terminal
+    /// capabilities are known. The first character of `mask` is used,
+    /// an empty mask is ignored keeping the current one.
+    pub fn set_mask(&mut self, mask: impl Display) {
+        if let Some(mask) = mask.to_string().chars().next() {
+            self.mask = mask;
+        }
+    }
+
+    /// Sets the maximum number of characters, further typing and pasting
+    /// beyond the limit is ignored.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.input.set_max_len(max_length);
+        self
+    }
+
     /// Sets the validation callback.
     pub fn validate<V>(mut self, validator: V) -> Self
     where
@@ -51,48 +124,435 @@ impl Password {
         self
     }
 
+    /// Asks to type the password once again with the given prompt, the prompt
+    /// is submitted only when both entries match. On a mismatch, the error
+    /// is shown and the second entry is cleared to retry.
+    ///
+    /// The confirmation prompt and its input are drawn below the header and
+    /// the masked first entry. The validation runs against the final agreed value.
+    pub fn with_confirmation(
+        mut self,
+        confirm_prompt: impl Display,
+        mismatch_err: impl Display,
+    ) -> Self {
+        self.confirmation = Some((confirm_prompt.to_string(), mismatch_err.to_string()));
+        self
+    }
+
+    /// Shows a strength meter under the input which is updated on every
+    /// keystroke, e.g. `.strength(PasswordStrength::score)` for the default scoring.
+    pub fn strength<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&str) -> PasswordStrength + 'static,
+    {
+        self.strength = Some(Box::new(f));
+        self
+    }
+
+    /// Updates the strength meter of the current input.
+    fn update_strength(&mut self) {
+        if let Some(strength) = &self.strength {
+            self.current_strength = Some(strength(&self.input.to_string()));
+        }
+    }
+
+    /// Sets the key toggling between the masked and the plain text input.
+    /// Default: `Ctrl+T`.
+    pub fn reveal_key(mut self, key: Key) -> Self {
+        self.reveal_key = Some(key);
+        self
+    }
+
+    /// Sets the generator of a strong password which fills the input
+    /// on `Ctrl+G`, e.g. for an account creation. Default: none.
+    pub fn with_generator<G>(mut self, generator: G) -> Self
+    where
+        G: Fn() -> String + 'static,
+    {
+        self.generator = Some(Box::new(generator));
+        self
+    }
+
+    /// Sets the key submitting the prompt instead of `Enter`, e.g. `Key::Tab`.
+    /// `Enter` is ignored then.
+    pub fn submit_key(mut self, key: Key) -> Self {
+        self.submit_key = Some(key);
+        self
+    }
+
+    /// Sets the help text which is shown in a box above the prompt on `?`.
+    ///
+    /// `?` is not typed into the input then.
+    pub fn help(mut self, help: impl Display) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+
+    /// Submits the password without the interaction, e.g. in a test,
+    /// drawing only the submitted prompt.
+    ///
+    /// It's validated (and confirmed) as if it were typed, a failure is
+    /// returned as [`io::ErrorKind::InvalidInput`] error.
+    pub fn preset_answer(mut self, password: impl Display) -> Self {
+        self.preset = Some(password.to_string());
+        self
+    }
+
+    /// Resolves the prompt with the [`Password::on_timeout`] action if no key
+    /// is pressed for the duration, the countdown restarts on every key.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout.duration = Some(timeout);
+        self
+    }
+
+    /// Sets what happens on the [`Password::timeout`]. Default: [`TimeoutAction::Cancel`].
+    pub fn on_timeout(mut self, action: TimeoutAction) -> Self {
+        self.timeout.action = action;
+        self
+    }
+
+    /// Ignores `Esc`, so the prompt can only be submitted. Default: `false`.
+    pub fn no_cancel(mut self, no_cancel: bool) -> Self {
+        self.no_cancel = no_cancel;
+        self
+    }
+
+    /// Discards the keys typed before the prompt shows up, so they can't
+    /// answer it by accident. Default: `false`, the keys typed ahead are kept.
+    pub fn flush_input(mut self, flush: bool) -> Self {
+        self.flush_input = flush;
+        self
+    }
+
     /// Starts the prompt interaction.
     pub fn interact(&mut self) -> io::Result<String> {
         <Self as PromptInteraction<String>>::interact(self)
     }
+
+    /// Starts the prompt interaction returning a [`PromptError`](crate::PromptError)
+    /// which tells the cancellation apart from the I/O failures.
+    pub fn interact2(&mut self) -> Result<String, crate::PromptError> {
+        Ok(self.interact()?)
+    }
+
+    /// Starts the prompt interaction on the given terminal instead of stderr.
+    pub fn interact_on(&mut self, term: &Term) -> io::Result<String> {
+        <Self as PromptInteraction<String>>::interact_on(self, &mut term.clone())
+    }
+
+    /// Starts the prompt interaction awaiting the keys from the stream.
+    #[cfg(feature = "async")]
+    pub async fn interact_async<S: crate::KeyStream>(
+        &mut self,
+        keys: &mut S,
+    ) -> io::Result<String> {
+        <Self as PromptInteraction<String>>::interact_async(self, keys).await
+    }
+
+    /// Returns the number of lines the prompt occupies on the terminal of
+    /// the given width, e.g. to reserve the space in a composite UI.
+    pub fn rendered_height(&mut self, width: usize) -> usize {
+        <Self as PromptInteraction<String>>::rendered_height(self, &State::Active, width)
+    }
+
+    /// Starts the prompt interaction drawing it to the given writer.
+    ///
+    /// The keys are read from the terminal, see [`Password::interact`].
+    pub fn interact_with_writer<W: Write>(&mut self, writer: &mut W) -> io::Result<String> {
+        <Self as PromptInteraction<String>>::interact_with_writer(self, writer)
+    }
 }
 
 impl PromptInteraction<String> for Password {
+    fn cancelable(&self) -> bool {
+        !self.no_cancel
+    }
+
+    fn flush_input(&self) -> bool {
+        self.flush_input
+    }
+
+    fn timeout(&self) -> Timeout {
+        self.timeout
+    }
+
+    fn on_timeout(&mut self) -> State<String> {
+        // A half-typed password is never the answer.
+        State::Cancel
+    }
+
+    fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    fn init(&mut self) {
+        self.update_strength();
+    }
+
     fn input(&mut self) -> Option<&mut StringCursor> {
-        Some(&mut self.input)
+        match self.confirming {
+            true => Some(&mut self.confirm_input),
+            false => Some(&mut self.input),
+        }
+    }
+
+    fn preset(&mut self) -> Option<State<String>> {
+        let password = self.preset.clone()?;
+        self.input.replace(&password);
+        if self.confirmation.is_some() {
+            self.confirm_input.replace(&password);
+            self.confirming = true;
+        }
+
+        let submit = self.submit_key.clone().unwrap_or(Key::Enter);
+        Some(self.on(&Event::Key(submit)))
     }
 
     fn on(&mut self, event: &Event) -> State<String> {
         let Event::Key(key) = event;
 
-        if *key == Key::Enter {
+        if key == self.submit_key.as_ref().unwrap_or(&Key::Enter) {
             if self.input.is_empty() {
                 return State::Error("Input required".to_string());
             }
 
+            if let Some((_, mismatch_err)) = &self.confirmation {
+                if !self.confirming {
+                    self.confirming = true;
+                    return State::Active;
+                }
+                if self.confirm_input.to_string() != self.input.to_string() {
+                    self.confirm_input.replace("");
+                    return State::Error(mismatch_err.clone());
+                }
+            }
+
             if let Some(validator) = &self.validate {
                 if let Err(err) = validator(&self.input.to_string()) {
+                    // Start over, the agreed value is not acceptable.
+                    self.confirming = false;
+                    self.confirm_input.replace("");
                     return State::Error(err);
                 }
             }
+            self.reveal = false;
             return State::Submit(self.input.to_string());
         }
 
+        // Ctrl+T
+        if key == self.reveal_key.as_ref().unwrap_or(&Key::Char('\u{14}')) {
+            self.reveal = !self.reveal;
+        }
+
+        // Ctrl+G
+        if *key == Key::Char('\u{7}') && !self.confirming {
+            if let Some(generator) = &self.generator {
+                self.input.replace(&generator());
+            }
+        }
+
+        self.update_strength();
         State::Active
     }
 
     fn render(&mut self, state: &State<String>) -> String {
-        let mut masked = self.input.clone();
-        for chr in masked.iter_mut() {
-            *chr = self.mask;
+        let confirm_prompt = match (&self.confirmation, state) {
+            (Some((confirm_prompt, _)), State::Active | State::Error(_)) if self.confirming => {
+                Some(confirm_prompt)
+            }
+            _ => None,
+        };
+        let input = match confirm_prompt {
+            Some(_) => &self.confirm_input,
+            None => &self.input,
+        };
+
+        let mut masked = input.clone();
+        let revealed = self.reveal && matches!(state, State::Active | State::Error(_));
+        if !revealed {
+            for chr in masked.iter_mut() {
+                *chr = self.mask;
+            }
         }
 
         let theme = THEME.lock().unwrap();
 
-        let line1 = theme.format_header(&state.into(), &self.prompt);
+        let mut line1 = theme.format_header(&state.into(), &self.prompt);
+        if let Some(confirm_prompt) = confirm_prompt {
+            let first = self
+                .mask
+                .to_string()
+                .repeat(self.input.to_string().chars().count());
+            line1 += &theme.format_password_confirm_header(&state.into(), &first, confirm_prompt);
+        }
+        let masked = fit_input(state, &masked, &theme.ellipsis());
         let line2 = theme.format_input(&state.into(), &masked);
+        let meter = match self.current_strength {
+            Some(strength) if !self.confirming => {
+                theme.format_password_strength(&state.into(), strength)
+            }
+            _ => String::new(),
+        };
         let line3 = theme.format_footer(&state.into());
 
-        line1 + &line2 + &line3
+        line1 + &line2 + &meter + &line3
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(password: &mut Password, text: &str) {
+        for chr in text.chars() {
+            password.handle_key(Key::Char(chr));
+        }
+    }
+
+    #[test]
+    fn typing_stops_at_the_max_length() {
+        let mut password = Password::new("Password").max_length(4);
+        password.init();
+
+        type_text(&mut password, "abcd");
+        type_text(&mut password, "e");
+        password.handle_key(Key::ArrowLeft);
+        type_text(&mut password, "f");
+        assert_eq!(
+            password.handle_key(Key::Enter).submitted().as_deref(),
+            Some("abcd")
+        );
+    }
+
+    #[test]
+    fn paste_is_truncated_at_the_max_length() {
+        let mut password = Password::new("Password").mask('*').max_length(8);
+        password.init();
+
+        type_text(&mut password, "correct horse battery staple");
+        let frame = password.render(&State::Active);
+        assert!(console::strip_ansi_codes(&frame).contains("********"));
+        assert!(!console::strip_ansi_codes(&frame).contains("*********"));
+        assert_eq!(
+            password.handle_key(Key::Enter).submitted().as_deref(),
+            Some("correct ")
+        );
+    }
+
+    #[test]
+    fn ctrl_g_fills_the_input_with_the_generated_password() {
+        let mut password = Password::new("Password").with_generator(|| "s3cr3t-Pa55".into());
+        password.init();
+
+        type_text(&mut password, "typed");
+        password.handle_key(Key::Char('\u{7}'));
+        assert_eq!(
+            password.handle_key(Key::Enter).submitted().as_deref(),
+            Some("s3cr3t-Pa55")
+        );
+    }
+
+    #[test]
+    fn ctrl_g_is_inert_without_a_generator() {
+        let mut password = Password::new("Password");
+        password.init();
+
+        type_text(&mut password, "typed");
+        password.handle_key(Key::Char('\u{7}'));
+        assert_eq!(
+            password.handle_key(Key::Enter).submitted().as_deref(),
+            Some("typed")
+        );
+    }
+
+    #[test]
+    fn submit_key_replaces_enter() {
+        let mut password = Password::new("Password").submit_key(Key::Tab);
+        password.init();
+
+        type_text(&mut password, "secret");
+        assert!(matches!(password.handle_key(Key::Enter), State::Active));
+        assert_eq!(
+            password.handle_key(Key::Tab).submitted().as_deref(),
+            Some("secret")
+        );
+    }
+
+    #[test]
+    fn set_mask_changes_the_rendered_mask() {
+        let mut password = Password::new("Password").mask('*');
+        password.init();
+        type_text(&mut password, "abc");
+
+        password.set_mask("#");
+        let frame = console::strip_ansi_codes(&password.render(&State::Active)).into_owned();
+        assert!(frame.contains("###"));
+        assert!(!frame.contains("***"));
+
+        password.set_mask("");
+        let frame = console::strip_ansi_codes(&password.render(&State::Active)).into_owned();
+        assert!(frame.contains("###"), "an empty mask keeps the current one");
+    }
+
+    #[test]
+    fn score_counts_the_length_and_the_kinds_of_characters() {
+        use PasswordStrength::*;
+
+        assert_eq!(PasswordStrength::score(""), Weak);
+        assert_eq!(PasswordStrength::score("abcdefgh"), Weak);
+        assert_eq!(PasswordStrength::score("abc123"), Weak);
+        assert_eq!(PasswordStrength::score("abcd1234"), Medium);
+        assert_eq!(PasswordStrength::score("abcdefghijk1"), Medium);
+        assert_eq!(PasswordStrength::score("abcdefghij1!"), Strong);
+        assert_eq!(PasswordStrength::score("Correct-Horse-7"), Strong);
+    }
+
+    #[test]
+    fn strength_meter_follows_the_typing_and_hides_on_submit() {
+        let mut password = Password::new("Password").strength(PasswordStrength::score);
+        password.init();
+        let frame = |password: &mut Password, state: &State<String>| {
+            console::strip_ansi_codes(&password.render(state)).into_owned()
+        };
+
+        assert!(frame(&mut password, &State::Active).contains("Weak"));
+        type_text(&mut password, "abcd1234");
+        assert!(frame(&mut password, &State::Active).contains("Medium"));
+        type_text(&mut password, "EFG!");
+        assert!(frame(&mut password, &State::Active).contains("Strong"));
+
+        let submit = State::Submit("abcd1234EFG!".to_string());
+        assert!(!frame(&mut password, &submit).contains("Strong"));
+        assert!(!frame(&mut password, &State::Cancel).contains("Strong"));
+    }
+
+    #[test]
+    fn accepted_timeout_never_submits_the_typed_password() {
+        let mut password = Password::new("Password").on_timeout(TimeoutAction::AcceptDefault);
+        password.init();
+        type_text(&mut password, "hunter");
+        assert!(matches!(password.timed_out(), State::Cancel));
+    }
+
+    #[test]
+    fn confirmation_is_drawn_below_the_masked_first_entry() {
+        let mut password = Password::new("Password")
+            .mask('#')
+            .with_confirmation("Confirm password", "Mismatch");
+        password.init();
+        type_text(&mut password, "abc");
+        password.handle_key(Key::Enter);
+        type_text(&mut password, "ab");
+
+        let frame = password.render(&State::Active);
+        let lines = console::strip_ansi_codes(&frame)
+            .lines()
+            .map(|line| line.trim_end().to_string())
+            .collect::<Vec<_>>();
+        assert!(lines[0].ends_with("  Password"), "{lines:?}");
+        assert!(lines[1].ends_with("  ###"), "{lines:?}");
+        assert!(lines[2].ends_with("  Confirm password"), "{lines:?}");
+        assert!(lines[3].contains("  ##"), "{lines:?}");
+        assert_eq!(lines.len(), 5);
     }
 }
@@ -0,0 +1,256 @@
+use std::{fmt::Display, io, time::Duration};
+
+use indicatif::ProgressStyle;
+
+use crate::{
+    output,
+    spinner::Spinner,
+    theme::{ThemeState, THEME},
+};
+
+/// A progress bar that renders determinate progress indication.
+///
+/// It's either started on its own with [`ProgressBar::start`] when the total
+/// amount of work is known, or obtained by converting a running [`Spinner`]
+/// with [`Spinner::into_progress`] once it becomes known, and can be converted
+/// back with [`ProgressBar::into_spinner`] keeping the message.
+///
+/// Implemented via theming of [`indicatif::ProgressBar`](https://docs.rs/indicatif).
+///
+/// # Example
+///
+/// ```
+/// let files = ["a.txt", "b.txt", "c.txt"];
+///
+/// let mut progress = cliclack::progress_bar();
+/// progress.start(files.len() as u64, "Uploading");
+/// for _file in files {
+///     // Upload the file...
+///     progress.inc(1);
+/// }
+/// progress.stop("Uploaded");
+/// ```
+pub struct ProgressBar {
+    bar: indicatif::ProgressBar,
+    grouped: bool,
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        let bar = indicatif::ProgressBar::new(0);
+        bar.set_draw_target(output::draw_target());
+        bar.enable_steady_tick(Duration::from_millis(100));
+        Self {
+            bar,
+            grouped: false,
+        }
+    }
+}
+
+impl ProgressBar {
+    /// Converts the running indicatif bar into a determinate one, `grouped`
+    /// is set for a task of a [`MultiProgress`].
+    pub(crate) fn from_bar(bar: indicatif::ProgressBar, len: u64, grouped: bool) -> Self {
+        bar.set_length(len);
+        let progress = Self { bar, grouped };
+        progress.apply_style();
+        progress
+    }
+
+    /// Applies the themed progress bar style to the indicatif bar.
+    fn apply_style(&self) {
+        let theme = THEME.lock().unwrap();
+
+        let mut template = theme.format_progress_start();
+        if self.grouped {
+            template.insert_str(0, &theme.format_multi_progress_gutter());
+        }
+
+        self.bar.set_style(
+            ProgressStyle::with_template(&template)
+                .unwrap()
+                .tick_chars(&theme.spinner_chars())
+                .progress_chars(&format!(
+                    "{}{}",
+                    theme.progress_fill_char(),
+                    theme.progress_empty_char()
+                )),
+        );
+    }
+
+    /// Starts the progress bar with the total amount of work.
+    pub fn start(&mut self, total: u64, message: impl Display) {
+        self.bar.set_length(total);
+        self.bar.set_position(0);
+        self.apply_style();
+        self.bar.set_message(message.to_string());
+    }
+
+    /// Advances the position of the progress bar.
+    pub fn inc(&self, delta: u64) {
+        self.bar.inc(delta);
+    }
+
+    /// Sets the position of the progress bar.
+    pub fn set_position(&self, pos: u64) {
+        self.bar.set_position(pos);
+    }
+
+    /// Sets the message of the progress bar.
+    pub fn set_message(&self, message: impl Display) {
+        self.bar.set_message(message.to_string());
+    }
+
+    /// Converts the progress bar back into an indeterminate spinner,
+    /// the message is kept.
+    pub fn into_spinner(self) -> Spinner {
+        self.bar.unset_length();
+        Spinner::from_bar(self.bar, self.grouped)
+    }
+
+    /// Stops the progress bar.
+    ///
+    /// In a [`MultiProgress`] group, the message stays in place of the bar,
+    /// so the other tasks are not disturbed.
+    pub fn stop(&mut self, message: impl Display) {
+        let theme = THEME.lock().unwrap();
+
+        if self.grouped {
+            let line =
+                theme.format_multi_progress_item_stop(&ThemeState::Submit, &message.to_string());
+            self.bar
+                .set_style(ProgressStyle::with_template("{msg}").unwrap());
+            self.bar.finish_with_message(line);
+            return;
+        }
+
+        // Workaround: the next line doesn't "jump" around while resizing the terminal.
+        self.bar
+            .println(theme.format_progress_stop(&message.to_string()));
+        self.bar.finish_and_clear();
+    }
+}
+
+/// A group of spinners and progress bars of the concurrent tasks stacked
+/// under each other with the frame bar on the left.
+///
+/// The tasks are `Send`, so they can be moved to and updated from
+/// the worker threads.
+///
+/// # Example
+///
+/// ```
+/// use std::thread;
+///
+/// let multi = cliclack::MultiProgress::default();
+///
+/// let workers = ["a.iso", "b.iso"].map(|file| {
+///     let mut progress = multi.add_progress(100);
+///     thread::spawn(move || {
+///         progress.start(100, format!("Downloading {file}"));
+///         progress.inc(100);
+///         progress.stop(format!("Downloaded {file}"));
+///     })
+/// });
+/// for worker in workers {
+///     worker.join().unwrap();
+/// }
+///
+/// multi.stop("All files downloaded").ok();
+/// ```
+pub struct MultiProgress {
+    multi: indicatif::MultiProgress,
+}
+
+impl Default for MultiProgress {
+    fn default() -> Self {
+        Self {
+            multi: indicatif::MultiProgress::with_draw_target(output::draw_target()),
+        }
+    }
+}
+
+impl MultiProgress {
+    /// Adds a spinner of a task of an unknown length to the group.
+    pub fn add_spinner(&self) -> Spinner {
+        let bar = self.multi.add(indicatif::ProgressBar::new_spinner());
+        bar.enable_steady_tick(Duration::from_millis(100));
+        Spinner::from_bar(bar, true)
+    }
+
+    /// Adds a progress bar of a task of the given length to the group.
+    pub fn add_progress(&self, len: u64) -> ProgressBar {
+        let bar = self.multi.add(indicatif::ProgressBar::new(len));
+        bar.enable_steady_tick(Duration::from_millis(100));
+        ProgressBar::from_bar(bar, len, true)
+    }
+
+    /// Stops the group drawing a single footer under the tasks, which are
+    /// expected to be stopped already.
+    pub fn stop(&self, message: impl Display) -> io::Result<()> {
+        let footer = THEME
+            .lock()
+            .unwrap()
+            .format_multi_progress_stop(&message.to_string());
+        output::term().write_str(&footer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{output::Recorder, theme::with_theme, Theme};
+
+    #[test]
+    fn spinner_converts_to_a_bar_continuing_from_its_state() {
+        let mut spinner = crate::spinner();
+        spinner.start("Downloading");
+
+        let progress = spinner.into_progress(100);
+        assert_eq!(progress.bar.length(), Some(100));
+        assert_eq!(progress.bar.message(), "Downloading");
+        progress.inc(30);
+
+        let spinner = progress.into_spinner();
+        let mut progress = spinner.into_progress(200);
+        assert_eq!(progress.bar.length(), Some(200));
+        assert_eq!(progress.bar.position(), 30);
+        assert_eq!(progress.bar.message(), "Downloading");
+        progress.stop("Downloaded");
+    }
+
+    #[test]
+    fn bar_is_drawn_with_the_theme_characters() {
+        struct Ascii;
+        impl Theme for Ascii {
+            fn progress_fill_char(&self) -> char {
+                '#'
+            }
+            fn progress_empty_char(&self) -> char {
+                '-'
+            }
+            fn progress_delimiters(&self) -> (String, String) {
+                ("[".into(), "]".into())
+            }
+        }
+
+        let recorder = Recorder::default();
+        with_theme(Ascii, || {
+            let mut progress = ProgressBar::default();
+            progress.bar.set_draw_target(recorder.target());
+            progress.start(10, "Copying");
+            progress.set_position(5);
+            progress.bar.tick();
+            progress.stop("Copied");
+        });
+
+        let bar = format!("[{}{}]", "#".repeat(15), "-".repeat(15));
+        let lines = recorder.lines();
+        assert!(
+            lines
+                .iter()
+                .any(|line| line.contains(&format!("Copying {bar} 5/10"))),
+            "{lines:?}"
+        );
+    }
+}
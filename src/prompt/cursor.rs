@@ -1,11 +1,16 @@
 use std::fmt::{Display, Formatter, Result};
 
-use zeroize::ZeroizeOnDrop;
+use console::measure_text_width;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+const ZERO_WIDTH_JOINER: char = '\u{200d}';
 
 #[derive(Default, ZeroizeOnDrop, Clone)]
 pub struct StringCursor {
     value: Vec<char>,
     cursor: usize,
+    selected: bool,
+    max_len: Option<usize>,
 }
 
 impl StringCursor {
@@ -13,74 +18,321 @@ impl StringCursor {
         self.value.is_empty()
     }
 
+    /// Returns the value as if the character were inserted at the cursor.
+    pub fn with_inserted(&self, chr: char) -> String {
+        if self.selected {
+            return chr.to_string();
+        }
+        let mut value = self.value.clone();
+        value.insert(self.cursor, chr);
+        String::from_iter(value)
+    }
+
     pub fn current(&self) -> Option<char> {
         self.value.get(self.cursor).copied()
     }
 
+    /// Marks the whole value as selected: the next insertion replaces it.
+    pub fn select_all(&mut self) {
+        self.selected = !self.value.is_empty();
+        self.cursor = self.value.len();
+    }
+
+    pub fn is_selected(&self) -> bool {
+        self.selected
+    }
+
+    /// Drops the selected value if any, returns `true` if it was selected.
+    fn take_selection(&mut self) -> bool {
+        if self.selected {
+            self.selected = false;
+            self.value.clear();
+            self.cursor = 0;
+            return true;
+        }
+        false
+    }
+
+    /// Limits the number of characters, further insertions are ignored.
+    pub fn set_max_len(&mut self, max_len: usize) {
+        self.max_len = Some(max_len);
+        self.value.truncate(max_len);
+        self.cursor = self.cursor.min(max_len);
+    }
+
     pub fn insert(&mut self, chr: char) {
+        self.take_selection();
+        if matches!(self.max_len, Some(max_len) if self.value.len() >= max_len) {
+            return;
+        }
         self.value.insert(self.cursor, chr);
         self.cursor += 1;
     }
 
+    /// Returns whether the character at the given position continues the
+    /// cluster of the previous one, e.g. a combining mark composed by an IME
+    /// or a part of a zero-width joiner sequence.
+    fn is_continuation(&self, i: usize) -> bool {
+        i > 0
+            && i < self.value.len()
+            && !self.value[i].is_control()
+            && (measure_text_width(&self.value[i].to_string()) == 0
+                || self.value[i - 1] == ZERO_WIDTH_JOINER)
+    }
+
+    /// Returns the start of the character cluster preceding the position.
+    fn prev_boundary(&self, mut i: usize) -> usize {
+        i = i.saturating_sub(1);
+        while self.is_continuation(i) {
+            i -= 1;
+        }
+        i
+    }
+
+    /// Returns the end of the character cluster starting at the position.
+    fn next_boundary(&self, mut i: usize) -> usize {
+        i = (i + 1).min(self.value.len());
+        while self.is_continuation(i) {
+            i += 1;
+        }
+        i
+    }
+
     pub fn move_left(&mut self) {
-        if self.cursor > 0 {
-            self.cursor -= 1;
+        if self.selected {
+            self.selected = false;
+            self.cursor = 0;
+        } else if self.cursor > 0 {
+            self.cursor = self.prev_boundary(self.cursor);
         }
     }
 
     pub fn move_right(&mut self) {
+        self.selected = false;
         if self.cursor < self.value.len() {
-            self.cursor += 1;
+            self.cursor = self.next_boundary(self.cursor);
         }
     }
 
+    /// Returns the start of the line containing the position.
+    fn line_start(&self, i: usize) -> usize {
+        self.value[..i]
+            .iter()
+            .rposition(|&chr| chr == '\n')
+            .map_or(0, |newline| newline + 1)
+    }
+
+    /// Returns the end of the line containing the position (before `\n`).
+    fn line_end(&self, i: usize) -> usize {
+        self.value[i..]
+            .iter()
+            .position(|&chr| chr == '\n')
+            .map_or(self.value.len(), |newline| i + newline)
+    }
+
+    /// Moves the cursor to the same column of the previous line of
+    /// a multiline value, or to the end of the line if it's shorter.
+    pub fn move_line_up(&mut self) {
+        self.selected = false;
+        let start = self.line_start(self.cursor);
+        if start == 0 {
+            return;
+        }
+        let column = self.cursor - start;
+        let prev_start = self.line_start(start - 1);
+        self.cursor = prev_start + column.min(start - 1 - prev_start);
+    }
+
+    /// Moves the cursor to the same column of the next line of
+    /// a multiline value, or to the end of the line if it's shorter.
+    pub fn move_line_down(&mut self) {
+        self.selected = false;
+        let end = self.line_end(self.cursor);
+        if end == self.value.len() {
+            return;
+        }
+        let column = self.cursor - self.line_start(self.cursor);
+        let next_end = self.line_end(end + 1);
+        self.cursor = end + 1 + column.min(next_end - end - 1);
+    }
+
+    /// Returns the start of the whitespace-delimited word before the position.
+    fn word_start(&self, mut i: usize) -> usize {
+        while i > 0 && self.value[i - 1].is_whitespace() {
+            i -= 1;
+        }
+        while i > 0 && !self.value[i - 1].is_whitespace() {
+            i -= 1;
+        }
+        i
+    }
+
+    /// Returns the end of the whitespace-delimited word after the position.
+    fn word_end(&self, mut i: usize) -> usize {
+        let len = self.value.len();
+        while i < len && self.value[i].is_whitespace() {
+            i += 1;
+        }
+        while i < len && !self.value[i].is_whitespace() {
+            i += 1;
+        }
+        i
+    }
+
+    pub fn move_word_left(&mut self) {
+        if self.selected {
+            self.selected = false;
+            self.cursor = 0;
+        } else {
+            self.cursor = self.word_start(self.cursor);
+        }
+    }
+
+    pub fn move_word_right(&mut self) {
+        self.selected = false;
+        self.cursor = self.word_end(self.cursor);
+    }
+
     pub fn move_home(&mut self) {
+        self.selected = false;
         self.cursor = 0;
     }
 
     pub fn move_end(&mut self) {
+        self.selected = false;
         self.cursor = self.value.len();
     }
 
+    /// Moves the cursor to the start of the current line of a multiline value.
+    pub fn move_line_home(&mut self) {
+        self.selected = false;
+        self.cursor = self.line_start(self.cursor);
+    }
+
+    /// Moves the cursor to the end of the current line of a multiline value.
+    pub fn move_line_end(&mut self) {
+        self.selected = false;
+        self.cursor = self.line_end(self.cursor);
+    }
+
     pub fn delete_left(&mut self) {
-        if self.value.is_empty() {
+        if self.take_selection() || self.value.is_empty() {
             return;
         }
 
         if self.cursor > 0 {
-            self.value.remove(self.cursor - 1);
-            self.cursor -= 1;
+            let start = self.prev_boundary(self.cursor);
+            self.value.drain(start..self.cursor);
+            self.cursor = start;
+        }
+    }
+
+    pub fn delete_word_left(&mut self) {
+        if self.take_selection() {
+            return;
         }
+
+        let start = self.word_start(self.cursor);
+        self.value.drain(start..self.cursor);
+        self.cursor = start;
     }
 
     pub fn delete_right(&mut self) {
-        if self.value.is_empty() {
+        if self.take_selection() || self.value.is_empty() {
             return;
         }
 
         if self.cursor < self.value.len() {
-            self.value.remove(self.cursor);
+            let end = self.next_boundary(self.cursor);
+            self.value.drain(self.cursor..end);
+        }
+    }
+
+    pub fn delete_to_end(&mut self) {
+        if self.take_selection() {
+            return;
         }
+
+        self.value.truncate(self.cursor);
     }
 
     pub fn extend(&mut self, string: &str) {
-        self.value.extend(string.chars());
+        let free = self.max_len.map_or(usize::MAX, |max_len| {
+            max_len.saturating_sub(self.value.len())
+        });
+        self.value.extend(string.chars().take(free));
+    }
+
+    /// Replaces the whole value wiping the previous one, the cursor moves to the end.
+    pub fn replace(&mut self, string: &str) {
+        self.value.zeroize();
+        self.selected = false;
+        self.extend(string);
+        self.cursor = self.value.len();
     }
 
     pub fn split(&self) -> (String, String, String) {
         let left = String::from_iter(&self.value[..self.cursor]);
 
-        let cursor = String::from_iter(&[self.current().unwrap_or(' ')]);
-
-        let right = if !self.value.is_empty() && self.cursor < self.value.len() - 1 {
-            String::from_iter(&self.value[self.cursor + 1..])
+        let (cursor, right) = if self.cursor < self.value.len() {
+            let end = self.next_boundary(self.cursor);
+            (
+                String::from_iter(&self.value[self.cursor..end]),
+                String::from_iter(&self.value[end..]),
+            )
         } else {
-            String::new()
+            (" ".to_string(), String::new())
         };
 
         (left, cursor, right)
     }
 
+    /// Returns the part of the value which fits the width keeping the cursor
+    /// in view, the cut off parts are replaced with the ellipsis.
+    pub fn truncated(&self, width: usize, ellipsis: &str) -> StringCursor {
+        let char_width = |chr: char| measure_text_width(&chr.to_string());
+        // The cursor at the end takes a cell as well.
+        let total = self.value.iter().map(|&chr| char_width(chr)).sum::<usize>() + 1;
+        if total <= width {
+            return self.clone();
+        }
+
+        let ellipsis_width = measure_text_width(ellipsis);
+        let len = self.value.len();
+        let (mut start, mut end) = (self.cursor, (self.cursor + 1).min(len));
+        let mut used = self
+            .value
+            .get(self.cursor)
+            .map_or(1, |&chr| char_width(chr));
+        // The cells of the end ellipsis go to the start if the end is in view.
+        for ellipses in [2, 1] {
+            let budget = width.saturating_sub(ellipses * ellipsis_width);
+            while start > 0 && used + char_width(self.value[start - 1]) <= budget {
+                start -= 1;
+                used += char_width(self.value[start]);
+            }
+            while end < len && used + char_width(self.value[end]) <= budget {
+                used += char_width(self.value[end]);
+                end += 1;
+            }
+            if end < len {
+                break;
+            }
+        }
+
+        let mut truncated = StringCursor::default();
+        truncated.selected = self.selected;
+        if start > 0 {
+            truncated.value.extend(ellipsis.chars());
+        }
+        truncated.cursor = truncated.value.len() + self.cursor - start;
+        truncated.value.extend(&self.value[start..end]);
+        if end < len {
+            truncated.value.extend(ellipsis.chars());
+        }
+        truncated
+    }
+
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut char> {
         self.value.iter_mut()
     }
@@ -91,3 +343,178 @@ impl Display for StringCursor {
         write!(f, "{}", String::from_iter(&self.value))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cursor(value: &str) -> StringCursor {
+        let mut cursor = StringCursor::default();
+        cursor.replace(value);
+        cursor
+    }
+
+    #[test]
+    fn typing_replaces_the_selected_value() {
+        let mut input = cursor("my-app");
+        input.select_all();
+        assert!(input.is_selected());
+
+        input.insert('x');
+        assert_eq!(input.to_string(), "x");
+        assert!(!input.is_selected());
+        input.insert('y');
+        assert_eq!(input.to_string(), "xy");
+    }
+
+    #[test]
+    fn deleting_clears_the_selected_value() {
+        let mut input = cursor("my-app");
+        input.select_all();
+        input.delete_left();
+        assert_eq!(input.to_string(), "");
+    }
+
+    #[test]
+    fn arrows_keep_the_selected_value_and_position_the_cursor() {
+        let mut input = cursor("app");
+        input.select_all();
+        input.move_left();
+        assert!(!input.is_selected());
+        input.insert('x');
+        assert_eq!(input.to_string(), "xapp");
+
+        let mut input = cursor("app");
+        input.select_all();
+        input.move_right();
+        input.insert('x');
+        assert_eq!(input.to_string(), "appx");
+    }
+
+    #[test]
+    fn truncated_keeps_the_fitting_value() {
+        let input = cursor("my-app");
+        let truncated = input.truncated(7, "…");
+        assert_eq!(truncated.to_string(), "my-app");
+        assert_eq!(truncated.cursor, 6);
+    }
+
+    #[test]
+    fn truncated_cuts_the_start_before_the_cursor_at_the_end() {
+        let input = cursor("abcdefghij");
+        let truncated = input.truncated(8, "…");
+        assert_eq!(truncated.to_string(), "…efghij");
+        assert_eq!(truncated.cursor, 7);
+    }
+
+    #[test]
+    fn truncated_cuts_both_ends_around_the_cursor() {
+        let mut input = cursor("abcdefghij");
+        for _ in 0..5 {
+            input.move_left();
+        }
+        let truncated = input.truncated(7, "..");
+        assert_eq!(truncated.to_string(), "..def..");
+        assert_eq!(truncated.value[truncated.cursor], 'f');
+    }
+
+    #[test]
+    fn truncated_counts_the_wide_chars() {
+        let input = cursor("日本語のテキスト");
+        let truncated = input.truncated(9, "…");
+        assert_eq!(truncated.to_string(), "…キスト");
+        assert_eq!(truncated.cursor, 4);
+    }
+
+    #[test]
+    fn composed_characters_are_inserted_as_one_cluster() {
+        let mut input = cursor("caf");
+        for chr in "e\u{301}".chars() {
+            input.insert(chr);
+        }
+        input.insert('!');
+        assert_eq!(input.to_string(), "cafe\u{301}!");
+
+        input.move_left();
+        input.move_left();
+        assert_eq!(input.split(), ("caf".into(), "e\u{301}".into(), "!".into()));
+
+        input.move_right();
+        input.delete_left();
+        assert_eq!(input.to_string(), "caf!");
+    }
+
+    #[test]
+    fn zero_width_joiner_sequences_are_moved_over_and_deleted_whole() {
+        let mut input = cursor("a\u{1f469}\u{200d}\u{1f4bb}b");
+        input.move_left();
+        input.move_left();
+        assert_eq!(input.split().1, "\u{1f469}\u{200d}\u{1f4bb}");
+
+        input.delete_right();
+        assert_eq!(input.to_string(), "ab");
+        assert_eq!(input.split().1, "b");
+    }
+
+    #[test]
+    fn word_moves_skip_the_whitespace_and_the_word() {
+        let mut input = cursor("cargo  add serde");
+        input.move_word_left();
+        assert_eq!(input.cursor, 11);
+        input.move_word_left();
+        assert_eq!(input.cursor, 7);
+        input.move_word_left();
+        input.move_word_left();
+        assert_eq!(input.cursor, 0, "stops at the start");
+
+        input.move_word_right();
+        assert_eq!(input.cursor, 5);
+        input.move_word_right();
+        assert_eq!(input.cursor, 10);
+        input.move_word_right();
+        input.move_word_right();
+        assert_eq!(input.cursor, 16, "stops at the end");
+    }
+
+    #[test]
+    fn delete_word_left_removes_the_word_and_the_trailing_whitespace() {
+        let mut input = cursor("cargo add serde ");
+        input.delete_word_left();
+        assert_eq!(input.to_string(), "cargo add ");
+        input.move_left();
+        input.delete_word_left();
+        assert_eq!(input.to_string(), "cargo  ");
+        assert_eq!(input.cursor, 6);
+    }
+
+    #[test]
+    fn line_moves_keep_the_column_or_stop_at_the_line_end() {
+        let mut input = cursor("abcdef\nxy\nlonger");
+        input.cursor = 4;
+
+        input.move_line_down();
+        assert_eq!(input.cursor, 9, "the end of the shorter line");
+        input.move_line_down();
+        assert_eq!(input.cursor, 12, "the column of the original line is lost");
+        input.move_line_up();
+        input.move_line_up();
+        assert_eq!(input.cursor, 2);
+
+        input.move_line_up();
+        assert_eq!(input.cursor, 2, "no line above");
+        input.cursor = 14;
+        input.move_line_down();
+        assert_eq!(input.cursor, 14, "no line below");
+    }
+
+    #[test]
+    fn line_home_and_end_stay_within_the_line() {
+        let mut input = cursor("abc\ndef\nghi");
+        input.cursor = 5;
+
+        input.move_line_home();
+        assert_eq!(input.cursor, 4);
+        input.move_line_end();
+        assert_eq!(input.cursor, 7);
+    }
+}
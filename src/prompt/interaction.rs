@@ -1,7 +1,52 @@
 use console::{Key, Term};
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 
-use super::cursor::StringCursor;
+#[cfg(feature = "async")]
+use super::timer::Timer;
+use super::{cursor::StringCursor, reader};
+use crate::output::HiddenCursor;
+use crate::theme::{ThemeState, THEME};
+
+/// Whether the decoded keys are printed above the prompt for debugging.
+pub(crate) static DEBUG_KEYS: AtomicBool = AtomicBool::new(false);
+
+/// Whether the answer is read as a line from stdin if it's not a terminal.
+pub(crate) static STDIN_FALLBACK: AtomicBool = AtomicBool::new(false);
+
+/// The maximum width of the rendering, `0` stands for the terminal width.
+pub(crate) static MAX_WIDTH: AtomicUsize = AtomicUsize::new(0);
+
+/// Returns the error of the interaction without a terminal.
+fn not_a_terminal() -> io::Error {
+    io::Error::new(io::ErrorKind::NotConnected, "Not a terminal")
+}
+
+/// Returns the width available for the rendering on the terminal.
+fn term_width(term: &Term) -> usize {
+    let width = term.size().1 as usize;
+    match MAX_WIDTH.load(Ordering::Relaxed) {
+        0 => width,
+        max_width => width.min(max_width),
+    }
+}
+
+/// Returns the width available for the rendering of the prompts on the output.
+pub(crate) fn render_width() -> usize {
+    term_width(&crate::output::term())
+}
+
+/// Truncates the input being edited to fit the line of the prompt keeping
+/// the cursor in view, see [`Theme::ellipsis`](crate::Theme::ellipsis).
+pub(crate) fn fit_input<T>(state: &State<T>, input: &StringCursor, ellipsis: &str) -> StringCursor {
+    match state {
+        State::Active | State::Error(_) => {
+            input.truncated(render_width().saturating_sub(3), ellipsis)
+        }
+        _ => input.clone(),
+    }
+}
 
 pub enum State<T> {
     Active,
@@ -10,11 +55,59 @@ pub enum State<T> {
     Error(String),
 }
 
+#[cfg(test)]
+impl<T> State<T> {
+    /// Returns the submitted value if the state is the submit one.
+    pub(crate) fn submitted(self) -> Option<T> {
+        match self {
+            State::Submit(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
 #[derive(PartialEq, Eq)]
 pub enum Event {
     Key(Key),
 }
 
+/// What the asynchronous interaction is woken by.
+#[cfg(feature = "async")]
+enum Wakeup {
+    Key(Option<io::Result<Key>>),
+    Background,
+    Timeout,
+}
+
+/// What the prompt does when no key is pressed within its timeout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TimeoutAction {
+    /// Cancels the prompt as if `Esc` were pressed.
+    #[default]
+    Cancel,
+    /// Submits the default (or the current) answer as if `Enter` were
+    /// pressed, the prompt is cancelled if the answer is not valid.
+    ///
+    /// [`Password`](crate::Password) is cancelled regardless, and
+    /// [`Number`](crate::Number) and [`Autocomplete`](crate::Autocomplete)
+    /// submit only their default value, never a half-typed one.
+    AcceptDefault,
+}
+
+/// The timeout of the prompt interaction, none by default.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Timeout {
+    pub duration: Option<Duration>,
+    pub action: TimeoutAction,
+}
+
+impl Timeout {
+    /// Returns the instant of the timeout counted from now, if it's set.
+    fn deadline(&self) -> Option<Instant> {
+        self.duration.map(|duration| Instant::now() + duration)
+    }
+}
+
 /// Wraps text to fit the terminal width.
 fn wrap(text: &str, width: usize) -> String {
     use textwrap::{core::Word, fill, Options, WordSeparator};
@@ -31,6 +124,31 @@ fn wrap(text: &str, width: usize) -> String {
     )
 }
 
+/// Returns the number of lines the frame occupies on the terminal of the given width.
+fn frame_height(frame: &str, width: usize) -> usize {
+    wrap(frame, width).lines().count()
+}
+
+/// Clears the last `n` lines written to the output (like [`Term::clear_last_lines`]),
+/// using the escape sequences, so it works for any writer.
+fn clear_last_lines(out: &mut impl Write, n: usize) -> io::Result<()> {
+    if n > 0 {
+        write!(out, "\x1b[{n}A{}\x1b[{n}A", "\r\x1b[2K\x1b[1B".repeat(n))?;
+    }
+    Ok(())
+}
+
+/// An asynchronous source of keys to drive the prompts, e.g. an adapter
+/// of the terminal event stream of an async runtime.
+#[cfg(feature = "async")]
+pub trait KeyStream {
+    /// Polls the next key, `None` means the stream is exhausted.
+    fn poll_next_key(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<io::Result<Key>>>;
+}
+
 /// A component that renders itself as a prompt and handles user input.
 ///
 /// Two methods are mandatory to implement:
@@ -50,87 +168,704 @@ pub trait PromptInteraction<T> {
         None
     }
 
-    /// Starts the interaction with the user via stderr.
+    /// Returns whether the typed character is inserted into the input cursor.
+    fn accept_char(&self, _chr: char) -> bool {
+        true
+    }
+
+    /// Returns whether the input spans multiple lines, so `Home` and `End`
+    /// move within the current line.
+    fn multiline(&self) -> bool {
+        false
+    }
+
+    /// Returns the help text shown above the prompt on `?`.
+    fn help(&self) -> Option<&str> {
+        None
+    }
+
+    /// Renders the prompt with the help box above if it's shown.
+    fn render_frame(&mut self, state: &State<T>, help_shown: bool) -> String {
+        let frame = self.render(state);
+        match self.help() {
+            Some(help) if help_shown => {
+                THEME
+                    .lock()
+                    .unwrap()
+                    .format_note(&ThemeState::Submit, "Help", help)
+                    + &frame
+            }
+            _ => frame,
+        }
+    }
+
+    /// Returns whether `Esc` cancels the prompt, `Ctrl+C` always does.
+    fn cancelable(&self) -> bool {
+        true
+    }
+
+    /// Returns whether the keys typed ahead are discarded before the first
+    /// rendering of the interaction.
+    fn flush_input(&self) -> bool {
+        false
+    }
+
+    /// Prepares the prompt state before the first rendering, e.g. applies
+    /// the initial values.
+    fn init(&mut self) {}
+
+    /// Returns the state of the first rendering.
+    fn initial_state(&mut self) -> State<T> {
+        State::Active
+    }
+
+    /// Returns the state the prompt is forced to without the interaction,
+    /// e.g. the submitted or failed preset answer, `None` starts the interaction.
+    fn preset(&mut self) -> Option<State<T>> {
+        None
+    }
+
+    /// Returns the timeout of waiting for a key.
+    fn timeout(&self) -> Timeout {
+        Timeout::default()
+    }
+
+    /// Returns the interval of redrawing the prompt while no key is pressed,
+    /// e.g. to show the items added in the background, `None` for never.
+    fn refresh_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Handles the refresh while no key is pressed, e.g. replays a partial
+    /// chord which has expired, returns the new state if it has changed.
+    fn on_idle(&mut self) -> Option<State<T>> {
+        None
+    }
+
+    /// Returns the state of the accepted default answer on the timeout.
+    fn on_timeout(&mut self) -> State<T> {
+        self.on(&Event::Key(Key::Enter))
+    }
+
+    /// Returns the final state once the timeout has elapsed, the prompt is
+    /// cancelled unless the accepted answer is submitted.
+    fn timed_out(&mut self) -> State<T> {
+        match self.timeout().action {
+            TimeoutAction::AcceptDefault => match self.on_timeout() {
+                submit @ State::Submit(_) => submit,
+                _ => State::Cancel,
+            },
+            TimeoutAction::Cancel => State::Cancel,
+        }
+    }
+
+    /// Returns the state of the answer read as a line from stdin, which is
+    /// not a terminal, see [`set_stdin_fallback`](crate::set_stdin_fallback).
+    fn answer_line(&mut self, _line: &str) -> State<T> {
+        State::Error("The prompt can't be answered from stdin".to_string())
+    }
+
+    /// Finishes the prompt with the preset answer drawing its last frame,
+    /// instead of the interaction, if the answer is set.
+    fn interact_preset<W: Write>(&mut self, out: &mut W) -> Option<io::Result<T>> {
+        let state = self.preset()?;
+        Some(self.finish_without_interaction(state, out))
+    }
+
+    /// Finishes the prompt with the answer read from stdin, instead of
+    /// the interaction, if stdin is not a terminal and the fallback is enabled.
+    fn interact_stdin<W: Write>(&mut self, out: &mut W) -> Option<io::Result<T>> {
+        if !STDIN_FALLBACK.load(Ordering::Relaxed) || io::stdin().is_terminal() {
+            return None;
+        }
+
+        let mut line = String::new();
+        let state = match io::stdin().read_line(&mut line) {
+            Ok(0) => {
+                return Some(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "No answer on stdin",
+                )))
+            }
+            Ok(_) => self.answer_line(line.trim_end_matches(['\r', '\n'])),
+            Err(err) => return Some(Err(err)),
+        };
+        Some(self.finish_without_interaction(state, out))
+    }
+
+    /// Draws the last frame of the state reached without the interaction.
+    fn finish_without_interaction<W: Write>(
+        &mut self,
+        state: State<T>,
+        out: &mut W,
+    ) -> io::Result<T> {
+        let mut frame = self.render(&state);
+        if MAX_WIDTH.load(Ordering::Relaxed) > 0 {
+            frame = wrap(&frame, render_width());
+        }
+        out.write_all(frame.as_bytes())?;
+        out.flush()?;
+
+        match state {
+            State::Submit(result) => Ok(result),
+            State::Error(err) => Err(io::Error::new(io::ErrorKind::InvalidInput, err)),
+            State::Cancel => Err(io::ErrorKind::Interrupted.into()),
+            State::Active => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "The answer is incomplete",
+            )),
+        }
+    }
+
+    /// Returns the number of lines the frame of the given state occupies
+    /// on the terminal of the given width.
+    fn rendered_height(&mut self, state: &State<T>, width: usize) -> usize {
+        self.init();
+        frame_height(&self.render(state), width)
+    }
+
+    /// Polls the background work of the prompt, e.g. a lazy loading, returns
+    /// `true` if the prompt has to be redrawn.
+    #[cfg(feature = "async")]
+    fn poll_background(&mut self, _cx: &mut std::task::Context<'_>) -> bool {
+        false
+    }
+
+    /// Starts the interaction with the user via the output terminal, stderr by default.
     fn interact(&mut self) -> io::Result<T> {
-        self.interact_on(&mut Term::stderr())
+        self.interact_on(&mut crate::output::term())
     }
 
     /// Starts the interaction with the user via the given terminal.
     fn interact_on(&mut self, term: &mut Term) -> io::Result<T> {
+        self.init();
+        if let Some(result) = self.interact_preset(term) {
+            return result;
+        }
+        if let Some(result) = self.interact_stdin(term) {
+            return result;
+        }
+
         if !term.is_term() {
-            return Err(io::ErrorKind::NotConnected.into());
+            return Err(not_a_terminal());
+        }
+        if self.flush_input() {
+            reader::flush_input()?;
         }
 
-        term.hide_cursor()?;
+        let cursor = HiddenCursor::new(term)?;
         let result = self.interact_on_prepared(term);
-        term.show_cursor()?;
+        cursor.show()?;
         result
     }
 
-    /// Starts the interaction with the user via the prepared terminal.
-    /// This is a common boilerplate code.
-    fn interact_on_prepared(&mut self, term: &mut Term) -> io::Result<T> {
-        let mut state = State::Active;
-        let mut prev_frame = String::new();
+    /// Starts the interaction reading the keys from the output terminal and drawing
+    /// the prompt to the given writer, e.g. an own region of a concurrent UI.
+    ///
+    /// The writer is expected to interpret the terminal escape sequences
+    /// which are used to redraw the prompt, otherwise all frames are kept
+    /// in the output one after another.
+    fn interact_with_writer<W: Write>(&mut self, writer: &mut W) -> io::Result<T> {
+        self.init();
+        if let Some(result) = self.interact_preset(writer) {
+            return result;
+        }
+        if let Some(result) = self.interact_stdin(writer) {
+            return result;
+        }
 
-        loop {
-            let frame = self.render(&state);
+        let term = crate::output::term();
+        if !term.is_term() {
+            return Err(not_a_terminal());
+        }
+        if self.flush_input() {
+            reader::flush_input()?;
+        }
+
+        let cursor = HiddenCursor::new(&term)?;
+        let result = self.interact_loop(&term, writer);
+        cursor.show()?;
+        result
+    }
+
+    /// Starts the interaction awaiting the keys from the asynchronous stream
+    /// instead of blocking on the terminal, the prompt is drawn to the output terminal.
+    #[cfg(feature = "async")]
+    async fn interact_async<S: KeyStream>(&mut self, keys: &mut S) -> io::Result<T> {
+        let mut term = crate::output::term();
 
-            if frame != prev_frame {
-                let prev_frame_check = wrap(&prev_frame, term.size().1 as usize);
+        self.init();
+        if let Some(result) = self.interact_preset(&mut term) {
+            return result;
+        }
+        let cursor = HiddenCursor::new(&term)?;
 
-                term.clear_last_lines(prev_frame_check.lines().count())?;
-                term.write_all(frame.as_bytes())?;
-                term.flush()?;
+        let mut state = self.initial_state();
+        let mut prev_frame = String::new();
+        let mut help_shown = false;
+        let timer = self.timeout().deadline().map(Timer::start);
 
-                prev_frame = frame;
+        let result = loop {
+            let width = term_width(&term);
+            let frame = self.render_frame(&state, help_shown);
+            if let Err(err) = redraw(frame, &mut prev_frame, width, &mut term) {
+                break Err(err);
             }
 
-            if let State::Submit(result) = state {
-                return Ok(result);
+            match state {
+                State::Submit(result) => break Ok(result),
+                State::Cancel => break Err(io::ErrorKind::Interrupted.into()),
+                _ => {}
             }
 
-            if let State::Cancel = state {
-                return Err(io::ErrorKind::Interrupted.into());
+            let next = std::future::poll_fn(|cx| {
+                if self.poll_background(cx) {
+                    return std::task::Poll::Ready(Wakeup::Background);
+                }
+                if let std::task::Poll::Ready(key) = keys.poll_next_key(cx) {
+                    return std::task::Poll::Ready(Wakeup::Key(key));
+                }
+                match &timer {
+                    Some(timer) if timer.poll_expired(cx) => {
+                        std::task::Poll::Ready(Wakeup::Timeout)
+                    }
+                    _ => std::task::Poll::Pending,
+                }
+            });
+            let key = match next.await {
+                Wakeup::Key(Some(Ok(key))) => key,
+                Wakeup::Key(Some(Err(err))) => break Err(err),
+                Wakeup::Key(None) => break Err(io::ErrorKind::UnexpectedEof.into()),
+                // The prompt has changed in the background: redraw.
+                Wakeup::Background => continue,
+                Wakeup::Timeout => {
+                    state = self.timed_out();
+                    continue;
+                }
+            };
+            if let (Some(timer), Some(deadline)) = (&timer, self.timeout().deadline()) {
+                timer.restart(deadline);
+            }
+            if let Err(err) = debug_key(&key, &mut prev_frame, width, &mut term) {
+                break Err(err);
             }
+            if key == Key::Char('?') && self.help().is_some() {
+                help_shown = !help_shown;
+                continue;
+            }
+            help_shown = false;
+            state = self.handle_key(key);
+        };
 
-            let key = term.read_key()?;
+        cursor.show()?;
+        result
+    }
 
-            if let Some(cursor) = self.input() {
-                match key {
-                    Key::Char(chr) if !chr.is_ascii_control() => {
-                        cursor.insert(chr);
-                    }
-                    Key::Backspace => {
-                        cursor.delete_left();
-                    }
-                    Key::Del => {
-                        cursor.delete_right();
-                    }
-                    Key::ArrowLeft => {
-                        cursor.move_left();
-                    }
-                    Key::ArrowRight => {
-                        cursor.move_right();
-                    }
-                    Key::Home => {
-                        cursor.move_home();
+    /// Starts the interaction with the user via the prepared terminal.
+    fn interact_on_prepared(&mut self, term: &mut Term) -> io::Result<T> {
+        let keys = term.clone();
+        self.interact_loop(&keys, term)
+    }
+
+    /// Reads the keys from the terminal and draws the frames to the output.
+    /// This is a common boilerplate code.
+    fn interact_loop<W: Write>(&mut self, term: &Term, out: &mut W) -> io::Result<T> {
+        let mut state = self.initial_state();
+        let mut prev_frame = String::new();
+        let mut help_shown = false;
+        let mut deadline = self.timeout().deadline();
+
+        loop {
+            let width = term_width(term);
+            redraw(
+                self.render_frame(&state, help_shown),
+                &mut prev_frame,
+                width,
+                out,
+            )?;
+
+            match state {
+                State::Submit(result) => return Ok(result),
+                State::Cancel => return Err(io::ErrorKind::Interrupted.into()),
+                _ => {}
+            }
+
+            let remaining =
+                deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+            let wait = match (remaining, self.refresh_interval()) {
+                (Some(remaining), Some(interval)) => Some(remaining.min(interval)),
+                (remaining, interval) => remaining.or(interval),
+            };
+            let key = match wait {
+                Some(wait) => match reader::read_key_timeout(term, wait)? {
+                    Some(key) => key,
+                    None if deadline.is_some_and(|deadline| Instant::now() >= deadline) => {
+                        state = self.timed_out();
+                        continue;
                     }
-                    Key::End => {
-                        cursor.move_end();
+                    // The refresh: the frame is redrawn with the background changes.
+                    None => {
+                        if let Some(next) = self.on_idle() {
+                            state = next;
+                        }
+                        continue;
                     }
-                    _ => {}
-                }
+                },
+                None => reader::read_key(term)?,
+            };
+            deadline = self.timeout().deadline();
+            debug_key(&key, &mut prev_frame, width, out)?;
+            if key == Key::Char('?') && self.help().is_some() {
+                help_shown = !help_shown;
+                continue;
             }
+            help_shown = false;
+            state = self.handle_key(key);
+        }
+    }
 
+    /// Applies the key to the input cursor and to the prompt itself.
+    fn handle_key(&mut self, key: Key) -> State<T> {
+        let accepted = matches!(key, Key::Char(chr) if self.accept_char(chr));
+        let multiline = self.multiline();
+
+        if let Some(cursor) = self.input() {
             match key {
-                Key::Escape => {
-                    state = State::Cancel;
+                Key::Char(chr) if !chr.is_ascii_control() && accepted => {
+                    cursor.insert(chr);
+                }
+                Key::Backspace => {
+                    cursor.delete_left();
+                }
+                Key::Del => {
+                    cursor.delete_right();
+                }
+                Key::ArrowLeft => {
+                    cursor.move_left();
+                }
+                Key::ArrowRight => {
+                    cursor.move_right();
+                }
+                // Alt+B, Alt+F: `Ctrl+Left/Right` can't be decoded by the terminal
+                // backend, so the readline bindings are used.
+                Key::UnknownEscSeq(ref seq) if seq == &['b'] => {
+                    cursor.move_word_left();
+                }
+                Key::UnknownEscSeq(ref seq) if seq == &['f'] => {
+                    cursor.move_word_right();
+                }
+                // Ctrl+W
+                Key::Char('\u{17}') => {
+                    cursor.delete_word_left();
+                }
+                // Ctrl+K
+                Key::Char('\u{b}') => {
+                    cursor.delete_to_end();
+                }
+                Key::Home if multiline => {
+                    cursor.move_line_home();
+                }
+                Key::End if multiline => {
+                    cursor.move_line_end();
                 }
-                other => {
-                    state = self.on(&Event::Key(other));
+                Key::Home => {
+                    cursor.move_home();
                 }
+                Key::End => {
+                    cursor.move_end();
+                }
+                _ => {}
             }
         }
+
+        match key {
+            Key::CtrlC => State::Cancel,
+            Key::Escape if self.cancelable() => State::Cancel,
+            other => self.on(&Event::Key(other)),
+        }
+    }
+}
+
+/// Draws the frame in place of the previous one if the frame has changed.
+fn redraw<W: Write>(
+    mut frame: String,
+    prev_frame: &mut String,
+    width: usize,
+    out: &mut W,
+) -> io::Result<()> {
+    if MAX_WIDTH.load(Ordering::Relaxed) > 0 {
+        frame = wrap(&frame, width);
+    }
+
+    if frame != *prev_frame {
+        clear_last_lines(out, frame_height(prev_frame, width))?;
+        out.write_all(frame.as_bytes())?;
+        out.flush()?;
+
+        *prev_frame = frame;
+    }
+
+    Ok(())
+}
+
+/// Draws the frame in place of the previous one which is the last output
+/// of the writer, e.g. to update a prompt after its submission.
+///
+/// Both frames are wrapped like the frames of the interaction.
+pub(crate) fn redraw_in_place<W: Write>(
+    out: &mut W,
+    prev_frame: String,
+    frame: String,
+) -> io::Result<()> {
+    let width = render_width();
+    let mut prev_frame = match MAX_WIDTH.load(Ordering::Relaxed) {
+        0 => prev_frame,
+        _ => wrap(&prev_frame, width),
+    };
+    redraw(frame, &mut prev_frame, width, out)
+}
+
+/// Prints the key in place of the frame if [`DEBUG_KEYS`] is enabled,
+/// the frame is drawn again below.
+fn debug_key<W: Write>(
+    key: &Key,
+    prev_frame: &mut String,
+    width: usize,
+    out: &mut W,
+) -> io::Result<()> {
+    if DEBUG_KEYS.load(Ordering::Relaxed) {
+        let theme = THEME.lock().unwrap();
+        let line = theme.format_log(&format!("{key:?}"), &theme.remark_symbol());
+        clear_last_lines(out, frame_height(prev_frame, width))?;
+        out.write_all(line.as_bytes())?;
+        prev_frame.clear();
+    }
+
+    Ok(())
+}
+
+/// Runs the future to completion on the current thread for the tests.
+///
+/// Panics if the pending future isn't woken for a few seconds, rather than
+/// hanging the test.
+#[cfg(all(test, feature = "async"))]
+pub(crate) fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    use std::sync::{atomic::AtomicBool, Arc};
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct Unpark {
+        thread: std::thread::Thread,
+        woken: AtomicBool,
+    }
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.woken.store(true, Ordering::SeqCst);
+            self.thread.unpark();
+        }
+    }
+
+    let unpark = Arc::new(Unpark {
+        thread: std::thread::current(),
+        woken: AtomicBool::new(false),
+    });
+    let waker = Waker::from(unpark.clone());
+    let mut cx = Context::from_waker(&waker);
+    let mut future = std::pin::pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !unpark.woken.swap(false, Ordering::SeqCst) {
+            let now = Instant::now();
+            assert!(now < deadline, "The pending future is not woken");
+            std::thread::park_timeout(deadline - now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Confirm, Input};
+
+    #[test]
+    fn prompts_draw_to_their_own_writers() {
+        let (mut first, mut second) = (Vec::new(), Vec::new());
+
+        let name: String = Input::new("Name")
+            .preset_answer("my-app")
+            .interact_with_writer(&mut first)
+            .unwrap();
+        let git = Confirm::new("Git?")
+            .preset_answer(true)
+            .interact_with_writer(&mut second)
+            .unwrap();
+
+        let (first, second) = (
+            String::from_utf8(first).unwrap(),
+            String::from_utf8(second).unwrap(),
+        );
+        assert_eq!((name.as_str(), git), ("my-app", true));
+        assert!(first.contains("Name") && first.contains("my-app"));
+        assert!(!first.contains("Git?"));
+        assert!(second.contains("Git?") && !second.contains("Name"));
+    }
+
+    #[test]
+    fn debug_keys_are_printed_above_the_frame_when_enabled() {
+        let mut out = Vec::new();
+        let mut prev_frame = "frame\n".to_string();
+
+        debug_key(&Key::ArrowUp, &mut prev_frame, 80, &mut out).unwrap();
+        assert!(out.is_empty());
+        assert_eq!(prev_frame, "frame\n");
+
+        crate::set_debug_keys(true);
+        let result = debug_key(&Key::ArrowUp, &mut prev_frame, 80, &mut out);
+        crate::set_debug_keys(false);
+        result.unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("\x1b[1A"), "the frame is cleared");
+        assert!(out.contains("ArrowUp"));
+        assert!(prev_frame.is_empty(), "the frame is drawn again below");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn prompts_run_on_the_given_terminal() {
+        use crate::{output, Select};
+
+        let own = output::Capture::default();
+        let (result, global) = output::capture(|| {
+            Select::new("Template")
+                .item("web", "Web", "")
+                .item("cli", "CLI", "")
+                .preset_answer("cli")
+                .interact_on(&own.term())
+        });
+        assert_eq!(result.unwrap(), "cli");
+        assert!(own.text().contains("Template") && own.text().contains("CLI"));
+        assert!(global.is_empty());
+
+        let err = Input::new("Name")
+            .interact_on::<String>(&own.term())
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotConnected);
+    }
+
+    /// A stream of the scripted keys.
+    #[cfg(feature = "async")]
+    struct Script(std::vec::IntoIter<Key>);
+
+    #[cfg(feature = "async")]
+    impl KeyStream for Script {
+        fn poll_next_key(
+            &mut self,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Option<io::Result<Key>>> {
+            std::task::Poll::Ready(self.0.next().map(Ok))
+        }
+    }
+
+    #[cfg(all(unix, feature = "async"))]
+    #[test]
+    fn async_prompts_are_driven_by_the_scripted_keys() {
+        use crate::output;
+
+        let keys = vec![
+            Key::Char('a'),
+            Key::Char('b'),
+            Key::Backspace,
+            Key::Char('c'),
+        ];
+        let mut script = Script([keys, vec![Key::Enter]].concat().into_iter());
+        let (name, frames) = output::capture(|| {
+            block_on(Input::new("Name").interact_async::<String, _>(&mut script))
+        });
+        assert_eq!(name.unwrap(), "ac");
+        assert!(frames.contains("Name"));
+
+        let mut script = Script(vec![Key::Char('y'), Key::Escape].into_iter());
+        let (result, _) = output::capture(|| {
+            block_on(Input::new("Name").interact_async::<String, _>(&mut script))
+        });
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::Interrupted);
+
+        let mut script = Script(vec![Key::Char('y')].into_iter());
+        let (result, _) = output::capture(|| {
+            block_on(Input::new("Name").interact_async::<String, _>(&mut script))
+        });
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[cfg(all(unix, feature = "async"))]
+    #[test]
+    fn question_mark_toggles_the_help_box_and_the_prompt_resumes() {
+        use crate::output;
+
+        let keys = vec![Key::Char('?'), Key::Char('a'), Key::Enter];
+        let mut script = Script(keys.clone().into_iter());
+        let (name, frames) = output::capture(|| {
+            let mut input = Input::new("Name").help("The name of the crate.");
+            block_on(input.interact_async::<String, _>(&mut script))
+        });
+        assert_eq!(name.unwrap(), "a", "`?` is not typed");
+        let help = frames.find("The name of the crate.").unwrap();
+        let last = frames.rfind("Name").unwrap();
+        assert!(help < last, "the prompt is drawn again after the help");
+        assert!(!frames[last..].contains("The name of the crate."));
+
+        let mut script = Script(keys.into_iter());
+        let (name, _) = output::capture(|| {
+            block_on(Input::new("Name").interact_async::<String, _>(&mut script))
+        });
+        assert_eq!(name.unwrap(), "?a", "typed without the help");
+    }
+
+    /// A stream of keys which are never pressed.
+    #[cfg(feature = "async")]
+    struct Idle;
+
+    #[cfg(feature = "async")]
+    impl KeyStream for Idle {
+        fn poll_next_key(
+            &mut self,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Option<io::Result<Key>>> {
+            std::task::Poll::Pending
+        }
+    }
+
+    #[cfg(all(unix, feature = "async"))]
+    #[test]
+    fn async_prompts_resolve_on_the_timeout() {
+        use crate::{output, Password};
+
+        let timeout = Duration::from_millis(50);
+        let (name, _) = output::capture(|| {
+            let mut input = Input::new("Name")
+                .default_value("my-app")
+                .timeout(timeout)
+                .on_timeout(TimeoutAction::AcceptDefault);
+            block_on(input.interact_async::<String, _>(&mut Idle))
+        });
+        assert_eq!(name.unwrap(), "my-app");
+
+        let (result, _) = output::capture(|| {
+            let mut input = Input::new("Name").timeout(timeout);
+            block_on(input.interact_async::<String, _>(&mut Idle))
+        });
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::Interrupted);
+
+        let (result, _) = output::capture(|| {
+            let mut password = Password::new("Password")
+                .timeout(timeout)
+                .on_timeout(TimeoutAction::AcceptDefault);
+            block_on(password.interact_async(&mut Idle))
+        });
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::Interrupted);
     }
 }
@@ -1,2 +1,7 @@
 pub mod cursor;
 pub mod interaction;
+pub mod reader;
+pub mod repeat;
+#[cfg(feature = "async")]
+pub mod timer;
+pub mod typeahead;
@@ -0,0 +1,239 @@
+use std::io;
+use std::time::Duration;
+
+use console::{Key, Term};
+
+/// Reads the key from the terminal.
+#[cfg(unix)]
+pub(crate) fn read_key(term: &Term) -> io::Result<Key> {
+    // The raw reading returns `Ctrl+C` as a key instead of raising SIGINT.
+    term.read_key_raw()
+}
+
+/// Reads the key waiting for it at most for the timeout, returns `None`
+/// if no key is pressed in time.
+///
+/// The terminal is polled in the raw mode, so a key is readable before
+/// `Enter`, and nothing is read past the prompt.
+#[cfg(unix)]
+pub(crate) fn read_key_timeout(term: &Term, timeout: Duration) -> io::Result<Option<Key>> {
+    let deadline = std::time::Instant::now() + timeout;
+    with_input(|fd| {
+        let _raw = RawMode::new(fd)?;
+        match wait_readable(fd, deadline)? {
+            true => read_key(term).map(Some),
+            false => Ok(None),
+        }
+    })
+}
+
+/// Discards the keys typed ahead, which are still buffered by the terminal.
+#[cfg(unix)]
+pub(crate) fn flush_input() -> io::Result<()> {
+    with_input(|fd| discard_input(fd))
+}
+
+/// Runs the closure with the terminal the keys are read from: stdin, or
+/// the controlling terminal if stdin is not one, like `console` does.
+#[cfg(unix)]
+fn with_input<R>(f: impl FnOnce(std::os::fd::BorrowedFd) -> io::Result<R>) -> io::Result<R> {
+    use std::io::IsTerminal;
+    use std::os::fd::AsFd;
+
+    let stdin = io::stdin();
+    match stdin.is_terminal() {
+        true => f(stdin.as_fd()),
+        false => f(std::fs::File::open("/dev/tty")?.as_fd()),
+    }
+}
+
+/// Waits until the terminal has input to read or the deadline passes,
+/// returns whether the input is readable.
+#[cfg(unix)]
+fn wait_readable(fd: std::os::fd::BorrowedFd, deadline: std::time::Instant) -> io::Result<bool> {
+    use rustix::event::{poll, PollFd, PollFlags, Timespec};
+
+    loop {
+        let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+        let timeout = Timespec::try_from(remaining).map_err(io::Error::other)?;
+        let mut fds = [PollFd::new(&fd, PollFlags::IN)];
+        match poll(&mut fds, Some(&timeout)) {
+            Ok(ready) => return Ok(ready > 0),
+            // Interrupted by a signal, e.g. the terminal resize.
+            Err(rustix::io::Errno::INTR) => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+/// Discards the input received by the terminal but not read yet.
+#[cfg(unix)]
+fn discard_input(fd: impl std::os::fd::AsFd) -> io::Result<()> {
+    rustix::termios::tcflush(fd, rustix::termios::QueueSelector::IFlush)?;
+    Ok(())
+}
+
+/// Switches the terminal input to the raw mode until it's dropped.
+///
+/// The output modes are kept, so the lines drawn meanwhile, e.g. by
+/// a spinner, still start at the left edge.
+#[cfg(unix)]
+struct RawMode<'fd> {
+    fd: std::os::fd::BorrowedFd<'fd>,
+    original: rustix::termios::Termios,
+}
+
+#[cfg(unix)]
+impl<'fd> RawMode<'fd> {
+    fn new(fd: std::os::fd::BorrowedFd<'fd>) -> io::Result<Self> {
+        use rustix::termios::{tcgetattr, tcsetattr, OptionalActions};
+
+        let original = tcgetattr(fd)?;
+        let mut raw = original.clone();
+        raw.make_raw();
+        raw.output_modes = original.output_modes;
+        tcsetattr(fd, OptionalActions::Now, &raw)?;
+        Ok(Self { fd, original })
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawMode<'_> {
+    fn drop(&mut self) {
+        use rustix::termios::{tcsetattr, OptionalActions};
+
+        tcsetattr(self.fd, OptionalActions::Now, &self.original).ok();
+    }
+}
+
+/// The keys read by the background reader, which is started by the first
+/// reading with a timeout, since the terminal can't be polled without
+/// the platform API here.
+///
+/// Once started, all keys are read through it, so no key is lost
+/// between the prompts.
+#[cfg(not(unix))]
+static KEYS: std::sync::Mutex<Option<std::sync::mpsc::Receiver<io::Result<Key>>>> =
+    std::sync::Mutex::new(None);
+
+/// Reads the key from the background reader if it's started, or from
+/// the terminal directly.
+#[cfg(not(unix))]
+pub(crate) fn read_key(term: &Term) -> io::Result<Key> {
+    match KEYS.lock().unwrap().as_ref() {
+        Some(keys) => keys.recv().unwrap_or_else(|_| Err(reader_stopped())),
+        // The raw reading returns `Ctrl+C` as a key instead of raising SIGINT.
+        None => term.read_key_raw(),
+    }
+}
+
+/// Reads the key waiting for it at most for the timeout, returns `None`
+/// if no key is pressed in time.
+#[cfg(not(unix))]
+pub(crate) fn read_key_timeout(term: &Term, timeout: Duration) -> io::Result<Option<Key>> {
+    use std::sync::mpsc::RecvTimeoutError;
+
+    let mut keys = KEYS.lock().unwrap();
+    let keys = keys.get_or_insert_with(|| spawn_reader(term.clone()));
+
+    match keys.recv_timeout(timeout) {
+        Ok(key) => key.map(Some),
+        Err(RecvTimeoutError::Timeout) => Ok(None),
+        Err(RecvTimeoutError::Disconnected) => Err(reader_stopped()),
+    }
+}
+
+/// Starts the background thread forwarding the terminal keys until
+/// the reading fails.
+#[cfg(not(unix))]
+fn spawn_reader(term: Term) -> std::sync::mpsc::Receiver<io::Result<Key>> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || loop {
+        let key = term.read_key_raw();
+        let failed = key.is_err();
+        if sender.send(key).is_err() || failed {
+            break;
+        }
+    });
+    receiver
+}
+
+/// Discards the keys typed ahead and read by the background reader already.
+#[cfg(not(unix))]
+pub(crate) fn flush_input() -> io::Result<()> {
+    if let Some(keys) = KEYS.lock().unwrap().as_ref() {
+        while keys.try_recv().is_ok() {}
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn reader_stopped() -> io::Error {
+    io::Error::new(io::ErrorKind::UnexpectedEof, "The key reader has stopped")
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use rustix::event::{poll, PollFd, PollFlags, Timespec};
+    use rustix::pty::{grantpt, openpt, ptsname, unlockpt, OpenptFlags};
+    use std::fs::File;
+    use std::os::fd::OwnedFd;
+
+    /// Opens a pseudo terminal, returns its controller and the terminal.
+    fn pty() -> (OwnedFd, File) {
+        let controller = openpt(OpenptFlags::RDWR | OpenptFlags::NOCTTY).unwrap();
+        grantpt(&controller).unwrap();
+        unlockpt(&controller).unwrap();
+        let name = ptsname(&controller, Vec::new()).unwrap();
+        let terminal = File::options()
+            .read(true)
+            .write(true)
+            .open(name.to_str().unwrap())
+            .unwrap();
+        (controller, terminal)
+    }
+
+    fn readable(terminal: &File, timeout: Duration) -> bool {
+        let mut fds = [PollFd::new(terminal, PollFlags::IN)];
+        let timeout = Timespec::try_from(timeout).unwrap();
+        poll(&mut fds, Some(&timeout)).unwrap() > 0
+    }
+
+    #[test]
+    fn discarded_input_is_not_read() {
+        let (controller, terminal) = pty();
+        rustix::io::write(&controller, b"yes\n").unwrap();
+        assert!(readable(&terminal, Duration::from_secs(1)));
+
+        discard_input(&terminal).unwrap();
+        assert!(!readable(&terminal, Duration::ZERO));
+    }
+
+    #[test]
+    fn raw_mode_polls_a_key_before_enter_and_is_restored() {
+        use std::os::fd::AsFd;
+        use std::time::Instant;
+
+        let (controller, terminal) = pty();
+        let original = rustix::termios::tcgetattr(&terminal).unwrap();
+        let fd = terminal.as_fd();
+
+        rustix::io::write(&controller, b"y").unwrap();
+        let soon = || Instant::now() + Duration::from_millis(50);
+        assert!(!wait_readable(fd, soon()).unwrap(), "waiting for the line");
+        {
+            let _raw = RawMode::new(fd).unwrap();
+            assert!(wait_readable(fd, soon()).unwrap());
+            let raw = rustix::termios::tcgetattr(&terminal).unwrap();
+            assert_eq!(
+                raw.output_modes, original.output_modes,
+                "the lines are still drawn"
+            );
+        }
+
+        let restored = rustix::termios::tcgetattr(&terminal).unwrap();
+        assert_eq!(restored.local_modes, original.local_modes);
+        assert_eq!(restored.output_modes, original.output_modes);
+    }
+}
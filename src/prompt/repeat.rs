@@ -0,0 +1,66 @@
+use std::time::{Duration, Instant};
+
+use console::Key;
+
+/// Drops the navigation keys repeated faster than the interval, e.g. fired
+/// by a held arrow key, so the selection doesn't overshoot.
+#[derive(Default)]
+pub(crate) struct KeyRepeat {
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl KeyRepeat {
+    pub fn interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    /// Returns whether the key is handled, the other keys than the arrows
+    /// are always handled.
+    pub fn accept(&mut self, key: &Key) -> bool {
+        if !matches!(
+            key,
+            Key::ArrowUp | Key::ArrowDown | Key::ArrowLeft | Key::ArrowRight
+        ) {
+            return true;
+        }
+
+        let now = Instant::now();
+        if matches!(self.last, Some(last) if now.duration_since(last) < self.interval) {
+            return false;
+        }
+        self.last = Some(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrows_repeated_within_the_interval_are_dropped() {
+        let mut repeat = KeyRepeat::default();
+        repeat.interval(Duration::from_secs(60));
+
+        assert!(repeat.accept(&Key::ArrowDown));
+        assert!(!repeat.accept(&Key::ArrowDown));
+        assert!(!repeat.accept(&Key::ArrowUp));
+        assert!(repeat.accept(&Key::Enter), "not a navigation key");
+        assert!(repeat.accept(&Key::Char('j')));
+    }
+
+    #[test]
+    fn arrows_after_the_interval_are_accepted() {
+        let mut repeat = KeyRepeat::default();
+        assert!(
+            (0..5).all(|_| repeat.accept(&Key::ArrowDown)),
+            "no interval"
+        );
+
+        repeat.interval(Duration::from_millis(10));
+        assert!(!repeat.accept(&Key::ArrowDown));
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(repeat.accept(&Key::ArrowDown));
+    }
+}
@@ -0,0 +1,109 @@
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Waker};
+use std::thread;
+use std::time::Instant;
+
+/// Wakes the asynchronous interaction at the deadline of the prompt timeout,
+/// since a key stream can't be awaited with a deadline.
+///
+/// The timer thread stops once the timer is dropped.
+pub(crate) struct Timer {
+    shared: Arc<(Mutex<TimerState>, Condvar)>,
+}
+
+struct TimerState {
+    deadline: Instant,
+    waker: Option<Waker>,
+    stopped: bool,
+}
+
+impl Timer {
+    /// Starts the timer expiring at the deadline.
+    pub(crate) fn start(deadline: Instant) -> Self {
+        let state = TimerState {
+            deadline,
+            waker: None,
+            stopped: false,
+        };
+        let shared = Arc::new((Mutex::new(state), Condvar::new()));
+        let timer = shared.clone();
+        thread::spawn(move || run(&timer));
+        Self { shared }
+    }
+
+    /// Moves the deadline, e.g. restarting the countdown on a key.
+    pub(crate) fn restart(&self, deadline: Instant) {
+        let (state, wakeup) = &*self.shared;
+        state.lock().unwrap().deadline = deadline;
+        wakeup.notify_one();
+    }
+
+    /// Returns whether the deadline has passed, otherwise the task is woken
+    /// once it passes.
+    pub(crate) fn poll_expired(&self, cx: &mut Context<'_>) -> bool {
+        let (state, wakeup) = &*self.shared;
+        let mut state = state.lock().unwrap();
+        if Instant::now() >= state.deadline {
+            return true;
+        }
+        state.waker = Some(cx.waker().clone());
+        wakeup.notify_one();
+        false
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        let (state, wakeup) = &*self.shared;
+        state.lock().unwrap().stopped = true;
+        wakeup.notify_one();
+    }
+}
+
+/// Waits for the deadline waking the task registered by then, until
+/// the timer is dropped.
+fn run(shared: &(Mutex<TimerState>, Condvar)) {
+    let (state, wakeup) = shared;
+    let mut state = state.lock().unwrap();
+    while !state.stopped {
+        let now = Instant::now();
+        if now < state.deadline {
+            let timeout = state.deadline - now;
+            state = wakeup.wait_timeout(state, timeout).unwrap().0;
+            continue;
+        }
+        match state.waker.take() {
+            Some(waker) => waker.wake(),
+            None => state = wakeup.wait(state).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prompt::interaction::block_on;
+    use std::task::Poll;
+    use std::time::Duration;
+
+    #[test]
+    fn task_is_woken_at_the_deadline() {
+        let start = Instant::now();
+        let timer = Timer::start(start + Duration::from_millis(50));
+        block_on(std::future::poll_fn(|cx| match timer.poll_expired(cx) {
+            true => Poll::Ready(()),
+            false => Poll::Pending,
+        }));
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn restart_moves_the_deadline() {
+        let timer = Timer::start(Instant::now());
+        let mut cx = Context::from_waker(Waker::noop());
+        assert!(timer.poll_expired(&mut cx));
+
+        timer.restart(Instant::now() + Duration::from_secs(60));
+        assert!(!timer.poll_expired(&mut cx));
+    }
+}
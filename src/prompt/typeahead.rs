@@ -0,0 +1,54 @@
+use std::time::{Duration, Instant};
+
+/// The inactivity after which the typed prefix starts over.
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Accumulates the characters typed in a list to jump to the first item
+/// whose label starts with them.
+#[derive(Default)]
+pub(crate) struct TypeAhead {
+    prefix: String,
+    last: Option<Instant>,
+    case_sensitive: bool,
+}
+
+impl TypeAhead {
+    pub fn case_sensitive(&mut self, case_sensitive: bool) {
+        self.case_sensitive = case_sensitive;
+    }
+
+    /// Drops the prefix typed before the timeout.
+    fn expire(&mut self) {
+        if matches!(self.last, Some(last) if last.elapsed() > TIMEOUT) {
+            self.prefix.clear();
+        }
+        self.last = Some(Instant::now());
+    }
+
+    /// Appends the typed character to the prefix.
+    pub fn push(&mut self, chr: char) {
+        self.expire();
+        self.prefix.push(chr);
+    }
+
+    /// Shortens the prefix, returns `false` if there is nothing to shorten.
+    pub fn pop(&mut self) -> bool {
+        self.expire();
+        self.prefix.pop().is_some()
+    }
+
+    /// Returns the position of the first label starting with the prefix.
+    pub fn find<'a>(&self, labels: impl Iterator<Item = &'a str>) -> Option<usize> {
+        if self.prefix.is_empty() {
+            return None;
+        }
+
+        let mut labels = labels;
+        if self.case_sensitive {
+            labels.position(|label| label.starts_with(&self.prefix))
+        } else {
+            let prefix = self.prefix.to_lowercase();
+            labels.position(|label| label.to_lowercase().starts_with(&prefix))
+        }
+    }
+}
@@ -1,11 +1,20 @@
 use std::fmt::Display;
-use std::io;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::task::Waker;
+use std::time::Duration;
 
-use console::Key;
+use console::{measure_text_width, Key, Style, Term};
 
 use crate::{
-    prompt::interaction::{Event, PromptInteraction, State},
-    theme::THEME,
+    keymap::{keymap_or_global, Action, Chords, KeyMap, Stroke},
+    prompt::{
+        interaction::{render_width, Event, PromptInteraction, State, Timeout, TimeoutAction},
+        repeat::KeyRepeat,
+        typeahead::TypeAhead,
+    },
+    state::stored_answer,
+    theme::{SelectListItem, SelectListLayout, THEME},
 };
 
 #[derive(Default)]
@@ -13,6 +22,88 @@ pub struct RadioButton<T: Default> {
     pub value: T,
     pub label: String,
     pub hint: String,
+    pub style: Option<Style>,
+    pub disabled: bool,
+}
+
+#[cfg(feature = "async")]
+type DescriptionFuture = std::pin::Pin<Box<dyn std::future::Future<Output = String>>>;
+#[cfg(feature = "async")]
+type DescriptionCallback<T> = Box<dyn Fn(&T) -> DescriptionFuture>;
+
+enum ItemChange<T: Default> {
+    Push(RadioButton<T>),
+    Remove(T),
+}
+
+/// The glyphs of the [`Select`] items.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SelectStyle {
+    /// Radio buttons (like `●` and `○`).
+    #[default]
+    Radio,
+    /// Checkboxes (like `◼` and `◻`), still only one item is checked.
+    Checkbox,
+}
+
+/// A handle to add and remove [`Select`] items while the prompt is open,
+/// e.g. from a thread discovering the devices.
+///
+/// The prompt is redrawn with the changes while it waits for a key,
+/// the active item stays the same where possible.
+pub struct SelectItems<T: Default> {
+    changes: Arc<Mutex<Vec<ItemChange<T>>>>,
+    /// Wakes the asynchronous interaction to redraw the prompt.
+    waker: Arc<Mutex<Option<Waker>>>,
+}
+
+impl<T: Default> Clone for SelectItems<T> {
+    fn clone(&self) -> Self {
+        Self {
+            changes: self.changes.clone(),
+            waker: self.waker.clone(),
+        }
+    }
+}
+
+impl<T: Default> SelectItems<T> {
+    fn new() -> Self {
+        Self {
+            changes: Default::default(),
+            waker: Default::default(),
+        }
+    }
+
+    /// Adds an item to the end of the list.
+    pub fn push(&self, value: T, label: impl Display, hint: impl Display) {
+        self.change(ItemChange::Push(RadioButton {
+            value,
+            label: label.to_string(),
+            hint: hint.to_string(),
+            style: None,
+            disabled: false,
+        }));
+    }
+
+    /// Removes the items with the given value.
+    pub fn remove(&self, value: T) {
+        self.change(ItemChange::Remove(value));
+    }
+
+    fn change(&self, change: ItemChange<T>) {
+        self.changes.lock().unwrap().push(change);
+        if let Some(waker) = self.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+
+    /// Stores the waker to be woken on the next change, returns `true`
+    /// if there are changes to be applied already.
+    #[cfg(feature = "async")]
+    fn register(&self, waker: &Waker) -> bool {
+        *self.waker.lock().unwrap() = Some(waker.clone());
+        !self.changes.lock().unwrap().is_empty()
+    }
 }
 
 /// A prompt that asks for one selection from a list of options.
@@ -22,6 +113,30 @@ pub struct Select<T: Default> {
     items: Vec<RadioButton<T>>,
     cursor: usize,
     initial_value: Option<T>,
+    prompt_id: Option<String>,
+    spacing: usize,
+    align_hints: bool,
+    columns: usize,
+    show_hint_on_submit: bool,
+    live_items: Option<SelectItems<T>>,
+    style: SelectStyle,
+    max_rows: usize,
+    offset: usize,
+    type_ahead: TypeAhead,
+    repeat: KeyRepeat,
+    no_cancel: bool,
+    flush_input: bool,
+    timeout: Timeout,
+    keymap: Option<KeyMap>,
+    chords: Chords,
+    #[cfg(feature = "async")]
+    description: Option<DescriptionCallback<T>>,
+    #[cfg(feature = "async")]
+    pending_description: Option<(usize, DescriptionFuture)>,
+    #[cfg(feature = "async")]
+    resolved_description: Option<(usize, String)>,
+    help: Option<String>,
+    preset: Option<T>,
 }
 
 impl<T> Select<T>
@@ -37,73 +152,1346 @@ where
     }
 
     /// Adds an item to the selection prompt.
+    ///
+    /// The `label` is what the user sees, including the submitted line,
+    /// while the `value` is what [`Select::interact`] returns.
     pub fn item(mut self, value: T, label: impl Display, hint: impl Display) -> Self {
         self.items.push(RadioButton {
             value,
             label: label.to_string(),
             hint: hint.to_string(),
+            style: None,
+            disabled: false,
+        });
+        self
+    }
+
+    /// Adds an item which is shown greyed out but can't be selected, e.g.
+    /// an option requiring an upgrade. The navigation skips it, except for
+    /// the grid layout where it just can't be submitted.
+    ///
+    /// The hint is always shown for the disabled item.
+    pub fn item_disabled(mut self, value: T, label: impl Display, hint: impl Display) -> Self {
+        self.items.push(RadioButton {
+            value,
+            label: label.to_string(),
+            hint: hint.to_string(),
+            style: None,
+            disabled: true,
+        });
+        self
+    }
+
+    /// Adds an item with the label colored with the given style regardless
+    /// of whether the item is active, e.g. green for "ok" and red for "failed".
+    pub fn item_styled(
+        mut self,
+        value: T,
+        label: impl Display,
+        hint: impl Display,
+        style: Style,
+    ) -> Self {
+        self.items.push(RadioButton {
+            value,
+            label: label.to_string(),
+            hint: hint.to_string(),
+            style: Some(style),
+            disabled: false,
         });
         self
     }
 
+    /// Aligns the hints into a column after the widest label. Default: `false`.
+    pub fn align_hints(mut self, align: bool) -> Self {
+        self.align_hints = align;
+        self
+    }
+
+    /// Lays the items out in a grid of the given number of columns, which is
+    /// handy for short items. Default: `1`.
+    ///
+    /// Arrow keys move the active item across the grid, the hints are not shown.
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// Sets the glyphs of the items, e.g. checkboxes for a single choice.
+    /// Default: [`SelectStyle::Radio`].
+    pub fn style(mut self, style: SelectStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Limits the number of the visible items, the list scrolls keeping the active
+    /// item in the middle and the navigation wraps around. Default: `0`, all items are visible.
+    ///
+    /// It's ignored in the grid layout, see [`Select::columns`].
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// Scrolls the visible window to keep the active item in its middle,
+    /// the window stays within the list at its ends.
+    fn scroll(&mut self) {
+        if self.max_rows == 0 {
+            return;
+        }
+        let last = self.items.len().saturating_sub(self.max_rows);
+        self.offset = self.cursor.saturating_sub(self.max_rows / 2).min(last);
+    }
+
+    /// Ignores the arrow keys repeated within the interval after the previous
+    /// one, e.g. `Duration::from_millis(50)`, so a held key on a fast terminal
+    /// doesn't overshoot the selection. Default: zero, all repeats move.
+    pub fn navigation_repeat(mut self, interval: Duration) -> Self {
+        self.repeat.interval(interval);
+        self
+    }
+
+    /// Makes the type-ahead jump to the item by the typed label prefix
+    /// case-sensitive. Default: `false`.
+    pub fn type_ahead_case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.type_ahead.case_sensitive(case_sensitive);
+        self
+    }
+
+    /// Keeps the hint of the selected item after its label on submit.
+    /// Default: `false`.
+    pub fn show_hint_on_submit(mut self, show: bool) -> Self {
+        self.show_hint_on_submit = show;
+        self
+    }
+
+    /// Returns a handle to add and remove items during the interaction.
+    pub fn items_handle(&mut self) -> SelectItems<T> {
+        self.live_items.get_or_insert_with(SelectItems::new).clone()
+    }
+
+    /// Applies the changes made with [`SelectItems`] keeping the active item.
+    fn sync_items(&mut self) {
+        let Some(live_items) = &self.live_items else {
+            return;
+        };
+
+        for change in live_items.changes.lock().unwrap().drain(..) {
+            match change {
+                ItemChange::Push(item) => self.items.push(item),
+                ItemChange::Remove(value) => {
+                    let mut i = 0;
+                    while i < self.items.len() {
+                        if self.items[i].value == value {
+                            self.items.remove(i);
+                            if i < self.cursor {
+                                self.cursor -= 1;
+                            }
+                        } else {
+                            i += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        self.cursor = self.cursor.min(self.items.len().saturating_sub(1));
+    }
+
     /// Sets the initially selected item by value.
     pub fn initial_value(mut self, value: T) -> Self {
         self.initial_value = Some(value);
         self
     }
 
+    /// Sets the identifier of the prompt to look up the initial item
+    /// with the global [`StateProvider`](crate::StateProvider).
+    ///
+    /// The stored answer is the label of the item, it's used only if
+    /// [`Select::initial_value`] is not set.
+    pub fn prompt_id(mut self, id: &str) -> Self {
+        self.prompt_id = Some(id.into());
+        self
+    }
+
+    /// Sets the number of blank lines rendered between the items. Default: `0`.
+    pub fn spacing(mut self, spacing: usize) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Sets the key map of the list navigation, e.g. to bind `g g` to
+    /// jump to the top. Default: the global one, see [`set_keymap`](crate::set_keymap).
+    pub fn keymap(mut self, keymap: KeyMap) -> Self {
+        self.keymap = Some(keymap);
+        self
+    }
+
+    /// Shows a description of the active item under the list which is fetched
+    /// lazily, e.g. from the network, a placeholder is shown meanwhile.
+    ///
+    /// The description is loaded only by [`Select::interact_async`].
+    #[cfg(feature = "async")]
+    pub fn lazy_description<F, Fut>(mut self, f: F) -> Self
+    where
+        F: Fn(&T) -> Fut + 'static,
+        Fut: std::future::Future<Output = String> + 'static,
+    {
+        self.description = Some(Box::new(move |value| Box::pin(f(value))));
+        self
+    }
+
+    /// Sets the help text which is shown in a box above the prompt on `?`.
+    pub fn help(mut self, help: impl Display) -> Self {
+        self.help = Some(help.to_string());
+        self
+    }
+
+    /// Submits the item with the given value without the interaction, e.g.
+    /// for automation, drawing only the submitted prompt.
+    ///
+    /// A value without an enabled item is returned as
+    /// [`io::ErrorKind::InvalidInput`] error.
+    pub fn preset_answer(mut self, value: T) -> Self {
+        self.preset = Some(value);
+        self
+    }
+
+    /// Resolves the prompt with the [`Select::on_timeout`] action if no key
+    /// is pressed for the duration, the countdown restarts on every key.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout.duration = Some(timeout);
+        self
+    }
+
+    /// Sets what happens on the [`Select::timeout`]. Default: [`TimeoutAction::Cancel`].
+    pub fn on_timeout(mut self, action: TimeoutAction) -> Self {
+        self.timeout.action = action;
+        self
+    }
+
+    /// Ignores `Esc`, so the prompt can only be submitted. Default: `false`.
+    pub fn no_cancel(mut self, no_cancel: bool) -> Self {
+        self.no_cancel = no_cancel;
+        self
+    }
+
+    /// Discards the keys typed before the prompt shows up, so they can't
+    /// answer it by accident. Default: `false`, the keys typed ahead are kept.
+    pub fn flush_input(mut self, flush: bool) -> Self {
+        self.flush_input = flush;
+        self
+    }
+
     /// Starts the prompt interaction.
     pub fn interact(&mut self) -> io::Result<T> {
-        for (i, item) in self.items.iter().enumerate() {
-            if let Some(initial_value) = &self.initial_value {
-                if initial_value == &item.value {
-                    self.cursor = i;
-                    break;
-                }
-            }
-        }
         <Self as PromptInteraction<T>>::interact(self)
     }
+
+    /// Starts the prompt interaction returning a [`PromptError`](crate::PromptError)
+    /// which tells the cancellation apart from the I/O failures.
+    pub fn interact2(&mut self) -> Result<T, crate::PromptError> {
+        Ok(self.interact()?)
+    }
+
+    /// Starts the prompt interaction on the given terminal instead of stderr.
+    pub fn interact_on(&mut self, term: &Term) -> io::Result<T> {
+        <Self as PromptInteraction<T>>::interact_on(self, &mut term.clone())
+    }
+
+    /// Starts the prompt interaction awaiting the keys from the stream.
+    #[cfg(feature = "async")]
+    pub async fn interact_async<S: crate::KeyStream>(&mut self, keys: &mut S) -> io::Result<T> {
+        <Self as PromptInteraction<T>>::interact_async(self, keys).await
+    }
+
+    /// Returns the number of lines the prompt occupies on the terminal of
+    /// the given width, e.g. to reserve the space in a composite UI.
+    pub fn rendered_height(&mut self, width: usize) -> usize {
+        <Self as PromptInteraction<T>>::rendered_height(self, &State::Active, width)
+    }
+
+    /// Starts the prompt interaction drawing it to the given writer.
+    ///
+    /// The keys are read from the terminal, see [`Select::interact`].
+    pub fn interact_with_writer<W: Write>(&mut self, writer: &mut W) -> io::Result<T> {
+        <Self as PromptInteraction<T>>::interact_with_writer(self, writer)
+    }
 }
 
-impl<T: Default + Clone> PromptInteraction<T> for Select<T> {
-    fn on(&mut self, event: &Event) -> State<T> {
-        let Event::Key(key) = event;
+impl<T: Default + Clone> Select<T> {
+    fn on_key(&mut self, key: &Key) -> State<T> {
+        match key {
+            Key::Char(chr) if !chr.is_ascii_control() => {
+                self.type_ahead.push(*chr);
+                return self.jump();
+            }
+            Key::Backspace if self.type_ahead.pop() => return self.jump(),
+            _ => {}
+        }
+
+        if self.columns > 1 {
+            return self.on_grid_key(key);
+        }
+
+        match key {
+            Key::ArrowLeft => self.move_up(),
+            Key::ArrowRight => self.move_down(),
+            _ => {}
+        }
+
+        State::Active
+    }
+
+    /// Moves the cursor to the previous enabled item, or a row up in the grid.
+    fn move_up(&mut self) {
+        let columns = self.columns;
+        if columns > 1 {
+            if self.cursor >= columns {
+                self.cursor -= columns;
+            }
+        } else if let Some(i) = self.next_enabled(self.cursor, false, self.max_rows > 0) {
+            self.cursor = i;
+        }
+    }
+
+    /// Moves the cursor to the next enabled item, or a row down in the grid.
+    fn move_down(&mut self) {
+        let (len, columns) = (self.items.len(), self.columns);
+        if columns > 1 {
+            if self.cursor + columns < len {
+                self.cursor += columns;
+            } else if self.cursor / columns < len.saturating_sub(1) / columns {
+                // The last row is partial: jump to its last item.
+                self.cursor = len - 1;
+            }
+        } else if let Some(i) = self.next_enabled(self.cursor, true, self.max_rows > 0) {
+            self.cursor = i;
+        }
+    }
+
+    /// Returns whether the item exists and isn't disabled.
+    fn is_selectable(&self, i: usize) -> bool {
+        matches!(self.items.get(i), Some(item) if !item.disabled)
+    }
+
+    /// Returns the nearest enabled item after (or before) the given one,
+    /// wrapping around the ends of the list if requested.
+    fn next_enabled(&self, from: usize, forward: bool, wrap: bool) -> Option<usize> {
+        let len = self.items.len();
+        let mut i = from;
+        for _ in 1..len {
+            i = match (forward, wrap) {
+                (true, _) if i + 1 < len => i + 1,
+                (true, true) => 0,
+                (false, _) if i > 0 => i - 1,
+                (false, true) => len - 1,
+                _ => return None,
+            };
+            if !self.items[i].disabled {
+                return Some(i);
+            }
+        }
+        None
+    }
+
+    fn on_grid_key(&mut self, key: &Key) -> State<T> {
+        let (len, columns) = (self.items.len(), self.columns);
+        let column = self.cursor % columns;
 
         match key {
-            Key::ArrowLeft | Key::ArrowUp => {
-                if self.cursor > 0 {
-                    self.cursor -= 1;
+            Key::ArrowLeft if column > 0 => {
+                self.cursor -= 1;
+            }
+            Key::ArrowRight if column + 1 < columns && self.cursor + 1 < len => {
+                self.cursor += 1;
+            }
+            _ => {}
+        }
+
+        State::Active
+    }
+
+    /// Moves the cursor to the first item matching the typed prefix.
+    fn jump(&mut self) -> State<T> {
+        // The empty labels of the disabled items never match the prefix.
+        let labels = self.items.iter().map(|item| match item.disabled {
+            true => "",
+            false => item.label.as_str(),
+        });
+        if let Some(i) = self.type_ahead.find(labels) {
+            self.cursor = i;
+        }
+
+        State::Active
+    }
+
+    /// Handles the resolved actions and keys until the prompt is resolved.
+    fn handle_strokes(&mut self, strokes: Vec<Stroke>) -> State<T> {
+        for stroke in strokes {
+            let state = match stroke {
+                Stroke::Action(action) => self.on_action(action),
+                Stroke::Key(key) => self.on_key(&key),
+            };
+            if !matches!(state, State::Active) {
+                return state;
+            }
+        }
+
+        State::Active
+    }
+
+    fn on_action(&mut self, action: Action) -> State<T> {
+        match action {
+            Action::MoveUp => self.move_up(),
+            Action::MoveDown => self.move_down(),
+            Action::Submit if self.is_selectable(self.cursor) => {
+                return State::Submit(self.items[self.cursor].value.clone())
+            }
+            Action::Cancel if !self.no_cancel => return State::Cancel,
+            Action::Toggle | Action::Submit | Action::Cancel => {}
+            Action::First => {
+                if let Some(i) = self.items.iter().position(|item| !item.disabled) {
+                    self.cursor = i;
                 }
             }
-            Key::ArrowRight | Key::ArrowDown => {
-                if self.cursor < self.items.len() - 1 {
-                    self.cursor += 1;
+            Action::Last => {
+                if let Some(i) = self.items.iter().rposition(|item| !item.disabled) {
+                    self.cursor = i;
                 }
             }
-            Key::Enter => return State::Submit(self.items[self.cursor].value.clone()),
-            _ => {}
         }
 
         State::Active
     }
+}
+
+impl<T: Default + Clone + Eq> PromptInteraction<T> for Select<T> {
+    fn cancelable(&self) -> bool {
+        !self.no_cancel
+    }
+
+    fn flush_input(&self) -> bool {
+        self.flush_input
+    }
+
+    fn timeout(&self) -> Timeout {
+        self.timeout
+    }
+
+    fn on_timeout(&mut self) -> State<T> {
+        self.sync_items();
+        match self.items.get(self.cursor) {
+            Some(item) if !item.disabled => State::Submit(item.value.clone()),
+            _ => State::Active,
+        }
+    }
+
+    fn help(&self) -> Option<&str> {
+        self.help.as_deref()
+    }
+
+    fn init(&mut self) {
+        let initial = match &self.initial_value {
+            Some(value) => self.items.iter().position(|item| &item.value == value),
+            None => stored_answer(&self.prompt_id)
+                .and_then(|label| self.items.iter().position(|item| item.label == label)),
+        };
+        if let Some(i) = initial {
+            self.cursor = i;
+        }
+        if !self.is_selectable(self.cursor) {
+            if let Some(i) = self.next_enabled(self.cursor, true, true) {
+                self.cursor = i;
+            }
+        }
+        self.scroll();
+    }
+
+    fn preset(&mut self) -> Option<State<T>> {
+        let value = self.preset.as_ref()?;
+        match self.items.iter().position(|item| &item.value == value) {
+            Some(i) if !self.items[i].disabled => {
+                self.cursor = i;
+                Some(State::Submit(self.items[i].value.clone()))
+            }
+            _ => Some(State::Error(
+                "The preset answer is not an option".to_string(),
+            )),
+        }
+    }
+
+    fn answer_line(&mut self, line: &str) -> State<T> {
+        let position = match line.trim() {
+            "" => Some(self.cursor),
+            label => self.items.iter().position(|item| item.label == label),
+        };
+        match position {
+            Some(i) if self.is_selectable(i) => {
+                self.cursor = i;
+                State::Submit(self.items[i].value.clone())
+            }
+            _ => State::Error("The answer is not an option".to_string()),
+        }
+    }
+
+    fn refresh_interval(&self) -> Option<Duration> {
+        let live = self.live_items.as_ref().map(|_| Duration::from_millis(100));
+        match self.chords.remaining(&keymap_or_global(&self.keymap)) {
+            Some(chord) => Some(live.map_or(chord, |live| live.min(chord))),
+            None => live,
+        }
+    }
+
+    #[cfg(feature = "async")]
+    fn poll_background(&mut self, cx: &mut std::task::Context<'_>) -> bool {
+        if matches!(&self.live_items, Some(items) if items.register(cx.waker())) {
+            return true;
+        }
+
+        let Some(description) = &self.description else {
+            return false;
+        };
+        if self.items.is_empty() {
+            return false;
+        }
+
+        let cursor = self.cursor;
+        if matches!(&self.resolved_description, Some((i, _)) if *i == cursor) {
+            return false;
+        }
+        if !matches!(&self.pending_description, Some((i, _)) if *i == cursor) {
+            self.pending_description = Some((cursor, description(&self.items[cursor].value)));
+        }
+
+        let Some((_, future)) = &mut self.pending_description else {
+            return false;
+        };
+        match future.as_mut().poll(cx) {
+            std::task::Poll::Ready(text) => {
+                self.pending_description = None;
+                self.resolved_description = Some((cursor, text));
+                true
+            }
+            std::task::Poll::Pending => false,
+        }
+    }
+
+    fn on(&mut self, event: &Event) -> State<T> {
+        let Event::Key(key) = event;
+
+        self.sync_items();
+
+        if !self.repeat.accept(key) {
+            return State::Active;
+        }
+
+        let keymap = keymap_or_global(&self.keymap);
+        let strokes = self.chords.feed(&keymap, key.clone());
+        let state = self.handle_strokes(strokes);
+        self.scroll();
+        state
+    }
+
+    fn on_idle(&mut self) -> Option<State<T>> {
+        let strokes = self.chords.expire(&keymap_or_global(&self.keymap));
+        match strokes.is_empty() {
+            true => None,
+            false => {
+                let state = self.handle_strokes(strokes);
+                self.scroll();
+                Some(state)
+            }
+        }
+    }
 
     fn render(&mut self, state: &State<T>) -> String {
+        self.sync_items();
+
         let theme = THEME.lock().unwrap();
 
         let line1 = theme.format_header(&state.into(), &self.prompt);
 
-        let mut line2 = String::new();
-        for (i, item) in self.items.iter().enumerate() {
-            line2.push_str(&theme.format_select_item(
-                &state.into(),
-                self.cursor == i,
-                &item.label,
-                &item.hint,
-            ));
+        let spacing = match state {
+            State::Active | State::Error(_) => self.spacing,
+            _ => 0,
+        };
+
+        let label_width = match state {
+            State::Active | State::Error(_) if self.align_hints => self
+                .items
+                .iter()
+                .map(|item| measure_text_width(&item.label))
+                .max()
+                .unwrap_or(0),
+            _ => 0,
+        };
+
+        let labels = self.items.iter().map(|item| {
+            let mut label = match &item.style {
+                Some(style) => style.apply_to(&item.label).to_string(),
+                None => item.label.clone(),
+            };
+            if !item.hint.is_empty() {
+                let padding = label_width.saturating_sub(measure_text_width(&item.label));
+                label.push_str(&" ".repeat(padding));
+            }
+            if let State::Submit(_) = state {
+                if self.show_hint_on_submit && !item.hint.is_empty() {
+                    let hint_style = theme.placeholder_style(&state.into());
+                    label = format!(
+                        "{label} {}",
+                        hint_style.apply_to(format!("({})", item.hint))
+                    );
+                }
+            }
+            label
+        });
+
+        let items = self
+            .items
+            .iter()
+            .zip(labels)
+            .map(|(item, label)| SelectListItem {
+                label: match item.disabled {
+                    true => item.label.clone(),
+                    false => label,
+                },
+                hint: item.hint.clone(),
+                disabled: item.disabled,
+            })
+            .collect::<Vec<_>>();
+
+        let active = matches!(state, State::Active | State::Error(_));
+        let columns = match active {
+            true => self.columns.max(1),
+            false => 1,
+        };
+        let len = self.items.len();
+        let window = match self.max_rows {
+            max_rows if max_rows > 0 && active && columns == 1 => {
+                let offset = self.offset.min(len.saturating_sub(max_rows));
+                offset..(offset + max_rows).min(len)
+            }
+            _ => 0..len,
+        };
+        let layout = SelectListLayout {
+            style: self.style,
+            columns,
+            hidden_above: window.start,
+            hidden_below: len - window.end,
+            spacing,
+            width: render_width(),
+        };
+        let mut line2 = theme.format_select_list(
+            &state.into(),
+            &items[window.clone()],
+            self.cursor.wrapping_sub(window.start),
+            &layout,
+        );
+        if self.items.is_empty() {
+            line2.push_str(&theme.format_select_empty(&state.into()));
+        }
+        #[cfg(feature = "async")]
+        if self.description.is_some() && !self.items.is_empty() {
+            let description = match &self.resolved_description {
+                Some((i, text)) if *i == self.cursor => Some(text.as_str()),
+                _ => None,
+            };
+            line2.push_str(&theme.format_select_description(&state.into(), description));
         }
         let line3 = theme.format_footer(&state.into());
 
         line1 + &line2 + &line3
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(select: &mut Select<i32>, state: &State<i32>) -> Vec<String> {
+        let frame = select.render(state);
+        console::strip_ansi_codes(&frame)
+            .lines()
+            .map(|line| line.trim_end().to_string())
+            .collect()
+    }
+
+    fn row(lines: &[String], label: &str) -> usize {
+        lines.iter().position(|line| line.ends_with(label)).unwrap()
+    }
+
+    #[test]
+    fn spacing_renders_blank_lines_between_items() {
+        let mut select = Select::new("Pick")
+            .item(1, "One", "")
+            .item(2, "Two", "")
+            .item(3, "Three", "")
+            .spacing(1);
+        select.init();
+
+        let lines = frame(&mut select, &State::Active);
+        let one = row(&lines, "One");
+        let two = row(&lines, "Two");
+        let three = row(&lines, "Three");
+
+        assert_eq!((two - one, three - two), (2, 2));
+        assert_eq!(lines[one + 1].chars().count(), 1, "only the bar is left");
+        assert_eq!(lines[two + 1].chars().count(), 1, "only the bar is left");
+    }
+
+    #[test]
+    fn spacing_is_tight_by_default_and_on_submit() {
+        let mut select = Select::new("Pick").item(1, "One", "").item(2, "Two", "");
+        select.init();
+
+        let lines = frame(&mut select, &State::Active);
+        let one = row(&lines, "One");
+        assert!(lines[one + 1].ends_with("Two"));
+
+        let submitted = frame(&mut select, &State::Submit(1));
+        let mut select = select.spacing(2);
+        assert_eq!(frame(&mut select, &State::Submit(1)), submitted);
+    }
+
+    #[test]
+    fn submit_renders_the_label_not_the_value() {
+        let mut select = Select::new("Pick")
+            .item(7, "Seven", "")
+            .item(8, "Eight", "");
+        select.init();
+
+        let lines = frame(&mut select, &State::Submit(7));
+        assert!(lines.iter().any(|line| line.ends_with("Seven")));
+        assert!(!lines.iter().any(|line| line.contains('7')));
+        assert!(!lines.iter().any(|line| line.contains("Eight")));
+    }
+
+    fn press(select: &mut Select<i32>, key: Key) -> State<i32> {
+        select.handle_key(key)
+    }
+
+    #[test]
+    fn enter_on_an_empty_select_is_ignored() {
+        let mut select = Select::<i32>::new("Pick");
+        select.init();
+
+        assert!(matches!(press(&mut select, Key::Enter), State::Active));
+        let lines = frame(&mut select, &State::Active);
+        assert!(lines.iter().any(|line| line.ends_with("No matches")));
+    }
+
+    #[test]
+    fn enter_is_ignored_once_the_items_are_removed() {
+        let mut select = Select::new("Pick").item(1, "One", "").item(2, "Two", "");
+        let items = select.items_handle();
+        select.init();
+        press(&mut select, Key::ArrowDown);
+
+        items.remove(1);
+        items.remove(2);
+        assert!(matches!(press(&mut select, Key::Enter), State::Active));
+        assert!(frame(&mut select, &State::Active)
+            .iter()
+            .any(|line| line.ends_with("No matches")));
+
+        items.push(3, "Three", "");
+        assert_eq!(press(&mut select, Key::Enter).submitted(), Some(3));
+    }
+
+    #[test]
+    fn styled_item_keeps_its_color_on_active_and_inactive_rows() {
+        console::set_colors_enabled(true);
+        let red = Style::new().red();
+        let mut select =
+            Select::new("Status")
+                .item(1, "Passed", "")
+                .item_styled(2, "Failed", "", red.clone());
+        select.init();
+
+        let failed = red.apply_to("Failed").to_string();
+        assert!(select.render(&State::Active).contains(&failed), "inactive");
+        press(&mut select, Key::ArrowDown);
+        assert!(select.render(&State::Active).contains(&failed), "active");
+    }
+
+    #[test]
+    fn live_changes_keep_the_active_item() {
+        let mut select = Select::new("Device")
+            .item(1, "One", "")
+            .item(2, "Two", "")
+            .item(3, "Three", "")
+            .item(4, "Four", "");
+        let items = select.items_handle();
+        select.init();
+        press(&mut select, Key::ArrowDown);
+        press(&mut select, Key::ArrowDown);
+
+        items.remove(1);
+        items.push(5, "Five", "");
+        select.sync_items();
+        assert_eq!(select.items[select.cursor].value, 3);
+
+        items.remove(3);
+        select.sync_items();
+        assert_eq!(select.items[select.cursor].value, 4, "the next one");
+
+        items.remove(4);
+        items.remove(5);
+        select.sync_items();
+        assert_eq!(select.items[select.cursor].value, 2, "the last one");
+    }
+
+    #[cfg(all(unix, feature = "async"))]
+    #[test]
+    fn item_pushed_mid_interaction_is_drawn_and_selectable() {
+        use crate::{output, prompt::interaction::block_on, KeyStream};
+        use std::task::{Context, Poll};
+
+        /// Waits for the first key until the prompt is woken by the change.
+        struct Keys(bool, std::vec::IntoIter<Key>);
+
+        impl KeyStream for Keys {
+            fn poll_next_key(&mut self, _cx: &mut Context<'_>) -> Poll<Option<io::Result<Key>>> {
+                if std::mem::take(&mut self.0) {
+                    return Poll::Pending;
+                }
+                Poll::Ready(self.1.next().map(Ok))
+            }
+        }
+
+        let mut select = Select::new("Device").item(1, "Wired", "");
+        let items = select.items_handle();
+        let discovery = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            items.push(2, "Wi-Fi", "");
+        });
+
+        let mut keys = Keys(true, vec![Key::ArrowDown, Key::Enter].into_iter());
+        let (device, frames) = output::capture(|| block_on(select.interact_async(&mut keys)));
+        discovery.join().unwrap();
+
+        assert_eq!(device.unwrap(), 2);
+        let drawn = frames.find("Wi-Fi").unwrap();
+        assert!(frames.find("Wired").unwrap() < drawn);
+    }
+
+    #[test]
+    fn aligned_hints_start_in_one_column() {
+        let hint_column = |select: &mut Select<i32>, label: &str| {
+            let lines = frame(select, &State::Active);
+            let line = lines.iter().find(|line| line.contains(label)).unwrap();
+            line.chars().position(|chr| chr == '(').unwrap()
+        };
+
+        let mut select = Select::new("Package manager")
+            .item(1, "npm", "default")
+            .item(2, "yarn classic", "v1")
+            .align_hints(true);
+        select.init();
+        let first = hint_column(&mut select, "npm");
+        press(&mut select, Key::ArrowDown);
+        assert_eq!(hint_column(&mut select, "yarn classic"), first);
+
+        let mut select = Select::new("Package manager")
+            .item(1, "npm", "default")
+            .item(2, "yarn classic", "v1");
+        select.init();
+        let first = hint_column(&mut select, "npm");
+        press(&mut select, Key::ArrowDown);
+        assert_ne!(
+            hint_column(&mut select, "yarn classic"),
+            first,
+            "ragged by default"
+        );
+    }
+
+    #[test]
+    fn hint_is_rendered_on_submit_only_when_enabled() {
+        let submitted = |show: bool| {
+            let mut select = Select::new("Runtime")
+                .item(1, "Node", "LTS")
+                .item(2, "Deno", "")
+                .show_hint_on_submit(show);
+            select.init();
+            frame(&mut select, &State::Submit(1)).join("\n")
+        };
+
+        assert!(submitted(true).contains("Node (LTS)"));
+        assert!(!submitted(false).contains("LTS"));
+    }
+
+    #[test]
+    fn no_cancel_keeps_the_select_active_on_escape() {
+        let mut select = Select::new("Pick").item(1, "One", "").no_cancel(true);
+        select.init();
+        assert!(matches!(press(&mut select, Key::Escape), State::Active));
+        assert_eq!(press(&mut select, Key::Enter).submitted(), Some(1));
+    }
+
+    #[test]
+    fn vim_chord_jumps_to_the_first_item() {
+        let mut select = Select::new("Pick")
+            .item(1, "One", "")
+            .item(2, "Two", "")
+            .item(3, "Three", "")
+            .keymap(KeyMap::vim());
+        select.init();
+
+        press(&mut select, Key::Char('G'));
+        assert_eq!(select.cursor, 2);
+        press(&mut select, Key::Char('g'));
+        assert_eq!(select.cursor, 2, "the chord is pending");
+        press(&mut select, Key::Char('g'));
+        assert_eq!(select.cursor, 0);
+    }
+
+    #[test]
+    fn grid_navigation_maps_the_arrows_to_the_cells() {
+        // 0 1
+        // 2 3
+        // 4
+        let mut select = Select::new("Pick").columns(2);
+        for i in 0..5 {
+            select = select.item(i, i.to_string(), "");
+        }
+        select.init();
+
+        let mut moves = |keys: &[Key]| {
+            for key in keys {
+                press(&mut select, key.clone());
+            }
+            select.cursor
+        };
+        assert_eq!(moves(&[Key::ArrowRight]), 1);
+        assert_eq!(moves(&[Key::ArrowRight]), 1, "the row end");
+        assert_eq!(moves(&[Key::ArrowDown]), 3);
+        assert_eq!(moves(&[Key::ArrowDown]), 4, "the last partial row");
+        assert_eq!(moves(&[Key::ArrowRight, Key::ArrowDown]), 4, "no cell");
+        assert_eq!(moves(&[Key::ArrowUp, Key::ArrowLeft]), 2);
+        assert_eq!(moves(&[Key::ArrowLeft]), 2, "the row start");
+        assert_eq!(moves(&[Key::ArrowUp, Key::ArrowUp]), 0);
+        assert_eq!(press(&mut select, Key::Enter).submitted(), Some(0));
+    }
+
+    #[test]
+    fn grid_renders_the_rows_of_columns() {
+        let mut select = Select::new("Pick")
+            .item(1, "One", "")
+            .item(2, "Two", "")
+            .item(3, "Three", "")
+            .columns(2);
+        select.init();
+
+        let lines = frame(&mut select, &State::Active);
+        let first = row(&lines, "Two");
+        assert!(lines[first].contains("One"));
+        assert!(lines[first + 1].ends_with("Three"));
+    }
+
+    #[test]
+    fn rendered_height_counts_the_item_lines() {
+        let mut select = Select::new("Pick")
+            .item(1, "One", "")
+            .item(2, "Two", "")
+            .item(3, "Three", "");
+        assert_eq!(select.rendered_height(80), 5);
+    }
+
+    #[test]
+    fn checkbox_style_checks_only_the_active_item() {
+        use crate::theme::ThemeState;
+
+        let symbol = |checked| {
+            let theme = THEME.lock().unwrap();
+            let symbol = theme.checkbox_symbol(&ThemeState::Active, checked, checked);
+            console::strip_ansi_codes(&symbol).into_owned()
+        };
+        let mut select = Select::new("Pick")
+            .item(1, "One", "")
+            .item(2, "Two", "")
+            .item(3, "Three", "")
+            .style(SelectStyle::Checkbox);
+        select.init();
+        press(&mut select, Key::ArrowDown);
+
+        let lines = frame(&mut select, &State::Active);
+        assert!(lines[row(&lines, "Two")].contains(&symbol(true)));
+        for label in ["One", "Three"] {
+            let line = &lines[row(&lines, label)];
+            assert!(line.contains(&symbol(false)) && !line.contains(&symbol(true)));
+        }
+        assert_eq!(press(&mut select, Key::Enter).submitted(), Some(2));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn long_hint_of_the_active_item_wraps_under_the_label() {
+        let hint = "a rather long description of the item which overflows the line";
+        let (lines, _) = crate::output::capture(|| {
+            crate::set_max_width(40);
+            let mut select = Select::new("Pick")
+                .item(1, "One", hint)
+                .item(2, "Two", hint);
+            select.init();
+            let lines = frame(&mut select, &State::Active);
+            crate::set_max_width(0);
+            lines
+        });
+
+        let one = row(&lines, "One");
+        let two = row(&lines, "Two");
+        assert!(two - one > 2, "the hint is wrapped to several lines");
+        for line in &lines[one + 1..two] {
+            assert!(line.chars().count() <= 40, "{line:?} overflows");
+            assert!(line.starts_with(&format!("{}    ", line.chars().next().unwrap())));
+        }
+        assert!(
+            lines[two + 1..]
+                .iter()
+                .all(|line| !line.contains("overflows")),
+            "only the active hint is wrapped"
+        );
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn lazy_description_shows_the_placeholder_until_resolved() {
+        use std::cell::Cell;
+        use std::future::Future;
+        use std::pin::Pin;
+        use std::rc::Rc;
+        use std::task::{Context, Poll};
+
+        /// A description resolved once the test lets it.
+        struct Delayed(Rc<Cell<bool>>, String);
+
+        impl Future for Delayed {
+            type Output = String;
+
+            fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<String> {
+                match self.0.get() {
+                    true => Poll::Ready(self.1.clone()),
+                    false => Poll::Pending,
+                }
+            }
+        }
+
+        let ready = Rc::new(Cell::new(false));
+        let pending = ready.clone();
+        let mut select = Select::new("Crate")
+            .item(1, "serde", "")
+            .lazy_description(move |id: &i32| Delayed(pending.clone(), format!("Crate #{id}")));
+        select.init();
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert!(!select.poll_background(&mut cx));
+        let lines = frame(&mut select, &State::Active);
+        assert!(lines.iter().any(|line| line.contains("Loading")));
+
+        ready.set(true);
+        assert!(select.poll_background(&mut cx), "redrawn once resolved");
+        let lines = frame(&mut select, &State::Active);
+        assert!(lines.iter().any(|line| line.ends_with("Crate #1")));
+        assert!(!lines.iter().any(|line| line.contains("Loading")));
+        assert!(!select.poll_background(&mut cx), "resolved only once");
+    }
+
+    #[test]
+    fn rapid_repeats_move_the_selection_once_per_interval() {
+        let items = |select: Select<i32>| {
+            select
+                .item(1, "One", "")
+                .item(2, "Two", "")
+                .item(3, "Three", "")
+                .item(4, "Four", "")
+        };
+        let mut select = items(Select::new("Pick").navigation_repeat(Duration::from_secs(60)));
+        select.init();
+        for _ in 0..3 {
+            press(&mut select, Key::ArrowDown);
+        }
+        assert_eq!(press(&mut select, Key::Enter).submitted(), Some(2));
+
+        let mut select = items(Select::new("Pick"));
+        select.init();
+        for _ in 0..3 {
+            press(&mut select, Key::ArrowDown);
+        }
+        assert_eq!(press(&mut select, Key::Enter).submitted(), Some(4));
+    }
+
+    #[test]
+    fn stdin_line_answers_with_the_item_label() {
+        let answer = |line: &str| {
+            let mut select = Select::new("Pick")
+                .item(1, "One", "")
+                .item(2, "Two", "")
+                .initial_value(2);
+            select.init();
+            select.answer_line(line)
+        };
+
+        assert_eq!(answer("One").submitted(), Some(1));
+        assert_eq!(answer(" One ").submitted(), Some(1));
+        assert_eq!(answer("").submitted(), Some(2));
+        assert!(matches!(answer("Three"), State::Error(_)));
+    }
+
+    #[test]
+    fn select_list_layout_is_overridden_by_the_theme() {
+        use crate::{theme::with_theme, SelectListItem, SelectListLayout, Theme, ThemeState};
+
+        struct Numbered;
+        impl Theme for Numbered {
+            fn format_select_list(
+                &self,
+                _state: &ThemeState,
+                items: &[SelectListItem],
+                active: usize,
+                layout: &SelectListLayout,
+            ) -> String {
+                let mut list = format!("[ {}\n", layout.hidden_above);
+                for (i, item) in items.iter().enumerate() {
+                    let marker = if i == active { ">" } else { " " };
+                    let disabled = if item.disabled { " (off)" } else { "" };
+                    list += &format!("{marker} {}. {}{disabled}\n", i + 1, item.label);
+                }
+                list + &format!("] {}\n", layout.hidden_below)
+            }
+        }
+
+        let lines = with_theme(Numbered, || {
+            let mut select = Select::new("Pick")
+                .item(1, "One", "")
+                .item_disabled(2, "Two", "")
+                .item(3, "Three", "")
+                .item(4, "Four", "")
+                .max_rows(2);
+            select.init();
+            select.handle_key(Key::ArrowDown);
+            frame(&mut select, &State::Active)
+        });
+
+        let open = lines.iter().position(|line| line == "[ 1").unwrap();
+        assert_eq!(
+            lines[open + 1..open + 4],
+            ["  1. Two (off)", "> 2. Three", "] 1"],
+            "{lines:?}"
+        );
+    }
+
+    const DAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+    fn days(max_rows: usize) -> Select<i32> {
+        let mut select = Select::new("Pick").max_rows(max_rows);
+        for (i, day) in DAYS.iter().enumerate() {
+            select = select.item(i as i32, day, "");
+        }
+        select.init();
+        select
+    }
+
+    /// Returns the visible labels of the list and the numbers of the hidden
+    /// items from its overflow lines (like `above 2`).
+    fn window(select: &mut Select<i32>) -> (Vec<String>, Vec<String>) {
+        let lines = frame(select, &State::Active);
+        let first = lines
+            .iter()
+            .position(|line| DAYS.iter().any(|day| line.ends_with(day)))
+            .unwrap();
+        let labels = lines
+            .iter()
+            .filter_map(|line| line.split_whitespace().last())
+            .filter(|label| DAYS.contains(label))
+            .map(String::from)
+            .collect();
+        let overflow = lines
+            .iter()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let hidden = line.strip_suffix(" more")?.split_whitespace().last()?;
+                let side = if i < first { "above" } else { "below" };
+                Some(format!("{side} {hidden}"))
+            })
+            .collect();
+        (labels, overflow)
+    }
+
+    #[test]
+    fn max_rows_window_keeps_the_active_item_in_the_middle() {
+        let mut select = days(3);
+        assert_eq!(
+            window(&mut select),
+            (
+                vec!["Mon".into(), "Tue".into(), "Wed".into()],
+                vec!["below 4".into()]
+            )
+        );
+
+        press(&mut select, Key::ArrowDown);
+        assert_eq!(window(&mut select).0, ["Mon", "Tue", "Wed"]);
+
+        press(&mut select, Key::ArrowDown);
+        assert_eq!(
+            window(&mut select),
+            (
+                vec!["Tue".into(), "Wed".into(), "Thu".into()],
+                vec!["above 1".into(), "below 3".into()]
+            )
+        );
+
+        for _ in 0..4 {
+            press(&mut select, Key::ArrowDown);
+        }
+        assert_eq!(select.cursor, 6);
+        assert_eq!(
+            window(&mut select),
+            (
+                vec!["Fri".into(), "Sat".into(), "Sun".into()],
+                vec!["above 4".into()]
+            )
+        );
+    }
+
+    #[test]
+    fn max_rows_navigation_wraps_around_the_ends() {
+        let mut select = days(4);
+
+        press(&mut select, Key::ArrowUp);
+        assert_eq!(select.cursor, 6);
+        assert_eq!(
+            window(&mut select),
+            (
+                vec!["Thu".into(), "Fri".into(), "Sat".into(), "Sun".into()],
+                vec!["above 3".into()]
+            )
+        );
+
+        press(&mut select, Key::ArrowDown);
+        assert_eq!(select.cursor, 0);
+        assert_eq!(
+            window(&mut select),
+            (
+                vec!["Mon".into(), "Tue".into(), "Wed".into(), "Thu".into()],
+                vec!["below 3".into()]
+            )
+        );
+
+        let mut select = Select::new("Pick").item(1, "One", "").item(2, "Two", "");
+        select.init();
+        press(&mut select, Key::ArrowUp);
+        assert_eq!(select.cursor, 0, "the list without max rows doesn't wrap");
+    }
+
+    #[test]
+    fn max_rows_window_is_dropped_on_submit() {
+        let mut select = days(3);
+        press(&mut select, Key::ArrowDown);
+        press(&mut select, Key::ArrowDown);
+        let lines = frame(&mut select, &State::Submit(2));
+        assert!(
+            !lines.iter().any(|line| line.ends_with(" more")),
+            "{lines:?}"
+        );
+    }
+
+    fn with_disabled() -> Select<i32> {
+        Select::new("Pick")
+            .item(1, "One", "")
+            .item_disabled(2, "Two", "")
+            .item(3, "Three", "")
+            .item_disabled(4, "Four", "")
+    }
+
+    #[test]
+    fn navigation_skips_the_disabled_items() {
+        let mut select = with_disabled();
+        select.init();
+
+        press(&mut select, Key::ArrowDown);
+        assert_eq!(select.cursor, 2);
+        press(&mut select, Key::ArrowDown);
+        assert_eq!(select.cursor, 2, "the last enabled item stays active");
+        press(&mut select, Key::ArrowUp);
+        press(&mut select, Key::ArrowUp);
+        assert_eq!(select.cursor, 0);
+
+        let mut select = with_disabled().max_rows(3);
+        select.init();
+
+        press(&mut select, Key::ArrowUp);
+        assert_eq!(
+            select.cursor, 2,
+            "the wrap-around skips the disabled last item"
+        );
+        press(&mut select, Key::ArrowDown);
+        assert_eq!(select.cursor, 0);
+    }
+
+    #[test]
+    fn initial_cursor_moves_off_a_disabled_item() {
+        let mut select = Select::new("Pick")
+            .item_disabled(1, "One", "")
+            .item(2, "Two", "");
+        select.init();
+        assert_eq!(select.cursor, 1);
+
+        let mut select = with_disabled().initial_value(2);
+        select.init();
+        assert_eq!(select.cursor, 2, "the next enabled item is active");
+
+        let mut select = with_disabled().initial_value(4);
+        select.init();
+        assert_eq!(select.cursor, 0, "the search wraps around");
+        assert_eq!(press(&mut select, Key::Enter).submitted(), Some(1));
+    }
+
+    #[test]
+    fn disabled_item_is_struck_through_and_hidden_on_submit() {
+        console::set_colors_enabled(true);
+        let mut select = with_disabled();
+        select.init();
+
+        let active = select.render(&State::Active);
+        assert!(active.contains(&console::style("Two").dim().strikethrough().to_string()));
+        assert!(!active.contains(&console::style("One").dim().strikethrough().to_string()));
+
+        let submitted = frame(&mut select, &State::Submit(1));
+        assert!(!submitted.iter().any(|line| line.contains("Two")));
+    }
+
+    #[test]
+    fn type_ahead_skips_the_disabled_labels() {
+        let mut select = Select::new("Pick")
+            .item(1, "Alpha", "")
+            .item_disabled(2, "Beta", "")
+            .item(3, "Bravo", "");
+        select.init();
+
+        press(&mut select, Key::Char('b'));
+        assert_eq!(select.cursor, 2);
+        press(&mut select, Key::Char('e'));
+        assert_eq!(select.cursor, 2, "the disabled label never matches");
+    }
+
+    #[test]
+    fn expired_chord_is_replayed_to_the_type_ahead_while_idle() {
+        let keymap = KeyMap::vim().chord_timeout(Duration::from_millis(10));
+        let mut select = Select::new("Pick")
+            .item(1, "Alpha", "")
+            .item(2, "Golf", "")
+            .keymap(keymap);
+        select.init();
+        assert_eq!(select.refresh_interval(), None);
+
+        press(&mut select, Key::Char('g'));
+        assert_eq!(select.cursor, 0, "the chord is pending");
+        assert!(select.refresh_interval().is_some(), "the chord is awaited");
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(matches!(select.on_idle(), Some(State::Active)));
+        assert_eq!(select.cursor, 1);
+        assert!(select.on_idle().is_none());
+    }
+}
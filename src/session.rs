@@ -0,0 +1,172 @@
+use std::fmt::Display;
+use std::io;
+
+/// Tracks the answers of a prompt sequence to render the final transcript.
+///
+/// ```
+/// use cliclack::{input, confirm, Session};
+///
+/// # fn test() -> std::io::Result<()> {
+/// let mut session = Session::new();
+///
+/// let name: String = session.record("Name", input("Project name").interact())?;
+/// let git = session.record("Git", confirm("Initialize git?").interact())?;
+///
+/// session.summary("Summary")?;
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
+#[derive(Default)]
+pub struct Session {
+    answers: Vec<(String, String)>,
+}
+
+impl Session {
+    /// Creates a new empty session.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the submitted value of a prompt under the given label and
+    /// passes the prompt result through.
+    ///
+    /// Nothing is recorded if the prompt fails or is cancelled.
+    pub fn record<T: Display>(
+        &mut self,
+        label: impl Display,
+        result: io::Result<T>,
+    ) -> io::Result<T> {
+        if let Ok(value) = &result {
+            self.answers.push((label.to_string(), value.to_string()));
+        }
+        result
+    }
+
+    /// Runs the prompt only if the condition holds for the answers so far,
+    /// which makes the sequence branch. The submitted value is recorded
+    /// as with [`Session::record`], `None` is returned for a skipped step.
+    ///
+    /// ```
+    /// use cliclack::{input, confirm, Session};
+    ///
+    /// # fn test() -> std::io::Result<()> {
+    /// let mut session = Session::new();
+    ///
+    /// session.record("Database", confirm("Use a database?").interact())?;
+    /// let url: Option<String> = session.record_if(
+    ///     "URL",
+    ///     |session| session.answer("Database") == Some("true"),
+    ///     || input("Database URL").interact(),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// # test().ok(); // Ignoring I/O runtime errors.
+    /// ```
+    pub fn record_if<T: Display>(
+        &mut self,
+        label: impl Display,
+        condition: impl FnOnce(&Self) -> bool,
+        prompt: impl FnOnce() -> io::Result<T>,
+    ) -> io::Result<Option<T>> {
+        if !condition(self) {
+            return Ok(None);
+        }
+        self.record(label, prompt()).map(Some)
+    }
+
+    /// Returns the recorded value of the given label if any.
+    pub fn answer(&self, label: &str) -> Option<&str> {
+        self.answers
+            .iter()
+            .find(|(recorded, _)| recorded == label)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the recorded labels and values in the order of submission.
+    pub fn answers(&self) -> &[(String, String)] {
+        &self.answers
+    }
+
+    /// Prints all recorded answers as a note with the given title.
+    pub fn summary(&self, title: impl Display) -> io::Result<()> {
+        let message = self
+            .answers
+            .iter()
+            .map(|(label, value)| format!("{label}: {value}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        crate::note(title, message)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::{confirm, input, output};
+
+    #[test]
+    fn summary_lists_each_answer() {
+        let (session, output) = output::capture(|| {
+            let mut session = Session::new();
+            let name = input("Project name")
+                .preset_answer("my-app")
+                .interact::<String>();
+            session.record("Name", name).unwrap();
+            let git = confirm("Initialize git?").preset_answer(true).interact();
+            session.record("Git", git).unwrap();
+            session
+                .record(
+                    "Skipped",
+                    Err::<String, _>(io::ErrorKind::Interrupted.into()),
+                )
+                .unwrap_err();
+            session.summary("Summary").unwrap();
+            session
+        });
+
+        assert_eq!(session.answer("Name"), Some("my-app"));
+        assert_eq!(session.answer("Skipped"), None);
+
+        let summary = &output[output.find("Summary").unwrap()..];
+        assert!(summary.contains("Name: my-app"), "{summary}");
+        assert!(summary.contains("Git: true"), "{summary}");
+        assert!(!summary.contains("Skipped"), "{summary}");
+    }
+
+    #[test]
+    fn record_if_skips_the_step_on_a_prior_answer() {
+        let run = |database: bool| {
+            output::capture(|| {
+                let mut session = Session::new();
+                let answer = confirm("Use a database?")
+                    .preset_answer(database)
+                    .interact();
+                session.record("Database", answer).unwrap();
+                let url = session
+                    .record_if(
+                        "URL",
+                        |session| session.answer("Database") == Some("true"),
+                        || {
+                            input("Database URL")
+                                .preset_answer("postgres://")
+                                .interact::<String>()
+                        },
+                    )
+                    .unwrap();
+                (url, session)
+            })
+            .0
+        };
+
+        let (url, session) = run(true);
+        assert_eq!(url.as_deref(), Some("postgres://"));
+        assert_eq!(session.answer("URL"), Some("postgres://"));
+
+        let (url, session) = run(false);
+        assert_eq!(url, None);
+        assert_eq!(session.answer("URL"), None);
+        assert_eq!(session.answers().len(), 1);
+    }
+}
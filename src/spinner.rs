@@ -1,45 +1,369 @@
-use std::{fmt::Display, time::Duration};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::{fmt::Display, thread, time::Duration};
+#[cfg(feature = "log")]
+use std::{io, sync::Mutex};
 
-use indicatif::{ProgressBar, ProgressStyle};
+#[cfg(feature = "log")]
+use indicatif::WeakProgressBar;
+use indicatif::{HumanDuration, ProgressBar, ProgressStyle};
 
-use crate::theme::THEME;
+use crate::{
+    output,
+    progress::ProgressBar as Progress,
+    theme::{ThemeState, THEME},
+};
+
+/// The minimum time in milliseconds a spinner is shown for before it stops.
+pub(crate) static MIN_DURATION: AtomicU64 = AtomicU64::new(0);
+
+/// The last started spinner, the log records are printed above it.
+#[cfg(feature = "log")]
+static RUNNING: Mutex<Option<WeakProgressBar>> = Mutex::new(None);
+
+/// Writes the text to the output suspending the running spinner if any,
+/// so the spinner is redrawn below the text.
+#[cfg(feature = "log")]
+pub(crate) fn write_above(text: &str) -> io::Result<()> {
+    let running = RUNNING
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(WeakProgressBar::upgrade);
+
+    match running {
+        Some(spinner) if !spinner.is_finished() => {
+            spinner.suspend(|| output::term().write_str(text))
+        }
+        _ => output::term().write_str(text),
+    }
+}
 
 /// A spinner that renders progress indication.
 ///
 /// Implemented via theming of [`indicatif::ProgressBar`](https://docs.rs/indicatif).
 pub struct Spinner {
     spinner: ProgressBar,
+    gutter: bool,
+    grouped: bool,
+    stop_template: Option<String>,
+    timer: bool,
 }
 
 impl Default for Spinner {
     fn default() -> Self {
         let spinner = ProgressBar::new_spinner();
+        spinner.set_draw_target(output::draw_target());
         spinner.enable_steady_tick(Duration::from_millis(100));
-        Self { spinner }
+        Self {
+            spinner,
+            gutter: false,
+            grouped: false,
+            stop_template: None,
+            timer: false,
+        }
     }
 }
 
 impl Spinner {
-    /// Starts the spinner.
-    pub fn start(&mut self, message: impl Display) {
+    /// Converts the running indicatif bar back into a spinner, `grouped`
+    /// is set for a task of a [`MultiProgress`](crate::MultiProgress).
+    pub(crate) fn from_bar(spinner: ProgressBar, grouped: bool) -> Self {
+        let spinner = Self {
+            spinner,
+            gutter: false,
+            grouped,
+            stop_template: None,
+            timer: false,
+        };
+        spinner.apply_style();
+        spinner
+    }
+
+    /// Applies the themed spinner style to the indicatif bar.
+    fn apply_style(&self) {
         let theme = THEME.lock().unwrap();
 
+        let mut template = theme.format_spinner_start();
+        if self.grouped {
+            template.insert_str(0, &theme.format_multi_progress_gutter());
+        } else if self.gutter {
+            template.insert_str(0, &theme.format_spinner_gutter());
+        }
+
         self.spinner.set_style(
-            ProgressStyle::with_template(&theme.format_spinner_start())
+            ProgressStyle::with_template(&template)
                 .unwrap()
                 .tick_chars(&theme.spinner_chars()),
         );
+    }
+
+    /// Prefixes the spinner line with the frame bar, so the gutter stays
+    /// continuous between the prompts. Default: `false`.
+    pub fn gutter(mut self, gutter: bool) -> Self {
+        self.gutter = gutter;
+        self
+    }
+
+    /// Appends the time since the start to the final message, like
+    /// `Compiled (3.2s)`.
+    pub fn with_timer(mut self) -> Self {
+        self.timer = true;
+        self
+    }
+
+    /// Sets the template of the final message, where `{msg}` is the message
+    /// passed on stop and `{elapsed}` is the time since the start, e.g.
+    /// `"{msg} in {elapsed}"` renders like `Done in 3 seconds`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the template has other placeholders or unmatched braces.
+    pub fn stop_template(mut self, template: &str) -> Self {
+        let rest = template.replace("{msg}", "").replace("{elapsed}", "");
+        assert!(
+            !rest.contains(['{', '}']),
+            "Invalid spinner stop template: {template:?}, only {{msg}} and {{elapsed}} are supported"
+        );
+        self.stop_template = Some(template.into());
+        self
+    }
 
+    /// Returns the final message rendered with the stop template if it's set.
+    fn stop_message(&self, message: impl Display) -> String {
+        let message = match &self.stop_template {
+            Some(template) => template
+                .replace(
+                    "{elapsed}",
+                    &HumanDuration(self.spinner.elapsed()).to_string(),
+                )
+                .replace("{msg}", &message.to_string()),
+            None => message.to_string(),
+        };
+
+        match self.timer {
+            true => {
+                let elapsed = THEME
+                    .lock()
+                    .unwrap()
+                    .format_spinner_elapsed(self.spinner.elapsed());
+                format!("{message} {elapsed}")
+            }
+            false => message,
+        }
+    }
+
+    /// Converts the spinner into a determinate progress bar with the given
+    /// total length, e.g. once the download size becomes known.
+    ///
+    /// The line isn't cleared and the message is kept.
+    pub fn into_progress(self, len: u64) -> Progress {
+        Progress::from_bar(self.spinner, len, self.grouped)
+    }
+
+    /// Draws the spinner to the given target, e.g. a test recorder.
+    #[cfg(test)]
+    pub(crate) fn draw_to(&self, target: indicatif::ProgressDrawTarget) {
+        self.spinner.set_draw_target(target);
+    }
+
+    /// Starts the spinner.
+    pub fn start(&mut self, message: impl Display) {
+        self.spinner.reset_elapsed();
+        self.apply_style();
         self.spinner.set_message(message.to_string());
+        #[cfg(feature = "log")]
+        {
+            *RUNNING.lock().unwrap() = Some(self.spinner.downgrade());
+        }
+    }
+
+    /// Prints a line above the spinner without corrupting it, e.g. a log record.
+    pub fn println(&self, message: impl Display) {
+        self.spinner.println(message.to_string());
+    }
+
+    /// Hides the spinner while the given closure writes to the terminal,
+    /// then restores it, e.g. to be used by a logger backend.
+    pub fn suspend<F: FnOnce() -> R, R>(&self, f: F) -> R {
+        self.spinner.suspend(f)
     }
 
     /// Stops the spinner.
+    ///
+    /// In a [`MultiProgress`](crate::MultiProgress) group, the message stays
+    /// in place of the spinner, so the other tasks are not disturbed.
     pub fn stop(&mut self, message: impl Display) {
+        self.stop_with_state(ThemeState::Submit, message);
+    }
+
+    /// Stops the spinner reporting a failure with the error symbol and
+    /// the message in red.
+    pub fn error(&mut self, message: impl Display) {
+        self.stop_with_state(ThemeState::Error(message.to_string()), message);
+    }
+
+    /// Stops the spinner reporting the cancellation, e.g. when the task
+    /// was interrupted.
+    pub fn cancel(&mut self, message: impl Display) {
+        self.stop_with_state(ThemeState::Cancel, message);
+    }
+
+    /// Waits until the spinner has been shown for the minimum duration set
+    /// with [`set_min_spinner_duration`](crate::set_min_spinner_duration).
+    fn hold_min_duration(&self) {
+        let min = Duration::from_millis(MIN_DURATION.load(Ordering::Relaxed));
+        hold(min, self.spinner.elapsed(), thread::sleep);
+    }
+
+    fn stop_with_state(&mut self, state: ThemeState, message: impl Display) {
+        self.hold_min_duration();
+        let message = self.stop_message(message);
         let theme = THEME.lock().unwrap();
 
+        if self.grouped {
+            let line = theme.format_multi_progress_item_stop(&state, &message.to_string());
+            self.spinner
+                .set_style(ProgressStyle::with_template("{msg}").unwrap());
+            self.spinner.finish_with_message(line);
+            return;
+        }
+
         // Workaround: the next line doesn't "jump" around while resizing the terminal.
         self.spinner
-            .println(theme.format_spinner_stop(&message.to_string()));
+            .println(theme.format_spinner_stop(&state, &message.to_string()));
         self.spinner.finish_and_clear();
     }
+
+    /// Stops the spinner printing the message as a plain line with the frame
+    /// bar, without the state symbol.
+    pub fn stop_plain(&mut self, message: impl Display) {
+        self.hold_min_duration();
+        let message = self.stop_message(message);
+        let theme = THEME.lock().unwrap();
+
+        // Workaround: the next line doesn't "jump" around while resizing the terminal.
+        self.spinner
+            .println(theme.format_spinner_stop_plain(&message.to_string()));
+        self.spinner.finish_and_clear();
+    }
+}
+
+/// Sleeps for the rest of the minimum duration if less time has elapsed.
+fn hold(min: Duration, elapsed: Duration, sleep: impl FnOnce(Duration)) {
+    let rest = min.saturating_sub(elapsed);
+    if !rest.is_zero() {
+        sleep(rest);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Recorder;
+
+    /// Starts the spinner drawing to a recorder.
+    fn start(mut spinner: Spinner, message: &str) -> (Spinner, Recorder) {
+        let recorder = Recorder::default();
+        spinner.draw_to(recorder.target());
+        spinner.start(message);
+        spinner.spinner.tick();
+        (spinner, recorder)
+    }
+
+    #[test]
+    fn gutter_prefixes_the_spinner_line() {
+        let bar = THEME.lock().unwrap().format_spinner_gutter();
+        let bar = console::strip_ansi_codes(&bar).into_owned();
+        for gutter in [true, false] {
+            let (mut spinner, recorder) = start(Spinner::default().gutter(gutter), "Installing");
+            let lines = recorder.lines();
+            let line = lines
+                .iter()
+                .rfind(|line| line.contains("Installing"))
+                .unwrap();
+            assert_eq!(line.starts_with(&bar), gutter, "{line:?}");
+            spinner.stop("Installed");
+        }
+    }
+
+    #[test]
+    fn stop_plain_prints_the_message_after_the_bar_only() {
+        let (stop, plain) = {
+            let theme = THEME.lock().unwrap();
+            let line = |text: String| {
+                let text = console::strip_ansi_codes(&text).into_owned();
+                text.lines().next().unwrap().to_string()
+            };
+            (
+                line(theme.format_spinner_stop(&ThemeState::Submit, "Done")),
+                line(theme.format_spinner_stop_plain("Done")),
+            )
+        };
+        assert_ne!(stop, plain, "the state symbol is replaced by the bar");
+
+        let (mut spinner, recorder) = start(Spinner::default(), "Installing");
+        spinner.stop_plain("Done");
+        let lines = recorder.lines();
+        let line = lines.iter().rfind(|line| line.contains("Done")).unwrap();
+        assert!(line.trim_end().ends_with(&plain), "{line:?}");
+    }
+
+    #[test]
+    fn stop_template_substitutes_the_elapsed_time() {
+        let spinner = Spinner::default().stop_template("{msg} in {elapsed}");
+        let (mut spinner, recorder) = start(spinner, "Installing");
+        spinner.stop("Done");
+
+        let lines = recorder.lines();
+        let line = lines.iter().rfind(|line| line.contains("Done")).unwrap();
+        let elapsed = HumanDuration(Duration::ZERO).to_string();
+        assert!(
+            line.trim_end().ends_with(&format!("Done in {elapsed}")),
+            "{line:?}"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid spinner stop template")]
+    fn stop_template_rejects_the_unknown_placeholders() {
+        let _ = Spinner::default().stop_template("{msg} after {duration}");
+    }
+
+    #[test]
+    fn with_timer_appends_the_elapsed_time() {
+        let (mut spinner, recorder) = start(Spinner::default().with_timer(), "Compiling");
+        spinner.stop("Compiled");
+
+        let lines = recorder.lines();
+        let line = lines
+            .iter()
+            .rfind(|line| line.contains("Compiled"))
+            .unwrap();
+        let (_, elapsed) = line.trim_end().rsplit_once("Compiled (").unwrap();
+        let secs = elapsed.strip_suffix("s)").unwrap();
+        assert!(secs.parse::<f64>().is_ok(), "{line:?}");
+    }
+
+    #[test]
+    fn stop_is_held_for_the_rest_of_the_min_duration() {
+        let ms = Duration::from_millis;
+        // The mock clock is advanced by the sleeping only.
+        let stop_at = |min: Duration, elapsed: Duration| {
+            let clock = std::cell::Cell::new(elapsed);
+            hold(min, elapsed, |rest| clock.set(clock.get() + rest));
+            clock.get()
+        };
+
+        assert_eq!(stop_at(ms(300), ms(100)), ms(300));
+        assert_eq!(
+            stop_at(ms(300), ms(500)),
+            ms(500),
+            "no holding of a long task"
+        );
+        assert_eq!(
+            stop_at(Duration::ZERO, ms(10)),
+            ms(10),
+            "no holding by default"
+        );
+    }
 }
@@ -0,0 +1,212 @@
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+/// Provides previously stored answers to seed the prompt defaults.
+///
+/// The crate only provides the hook, the storage (a state file, a database,
+/// etc.) is up to the caller. The provider is consulted for the prompts which
+/// have an identifier set with a `prompt_id` method, e.g. [`Input::prompt_id`](crate::Input::prompt_id).
+///
+/// [`Password`](crate::Password) has no identifier on purpose, so the secrets
+/// never end up in the storage.
+///
+/// A generic implementation for `Fn(&str) -> Option<String>` is provided.
+pub trait StateProvider {
+    /// Returns the stored answer of the prompt by its identifier.
+    fn get(&self, prompt_id: &str) -> Option<String>;
+}
+
+impl<F> StateProvider for F
+where
+    F: Fn(&str) -> Option<String>,
+{
+    fn get(&self, prompt_id: &str) -> Option<String> {
+        self(prompt_id)
+    }
+}
+
+/// The global state provider instance (singleton).
+///
+/// It can be set with [`set_state_provider`] function.
+static STATE_PROVIDER: Lazy<Mutex<Option<Box<dyn StateProvider + Send + Sync>>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// Sets the global state provider, which is used by all prompts
+/// with an identifier to look up their default values.
+///
+/// ```
+/// use cliclack::{input, set_state_provider};
+///
+/// # fn test() -> std::io::Result<()> {
+/// set_state_provider(|id: &str| match id {
+///     "name" => Some("my-app".to_string()),
+///     _ => None,
+/// });
+///
+/// let name: String = input("Project name").prompt_id("name").interact()?;
+/// # Ok(())
+/// # }
+/// # test().ok(); // Ignoring I/O runtime errors.
+/// ```
+///
+/// See [`reset_state_provider`] for removing the provider.
+pub fn set_state_provider<P: StateProvider + Sync + Send + 'static>(provider: P) {
+    *STATE_PROVIDER.lock().unwrap() = Some(Box::new(provider));
+}
+
+/// Removes the global state provider.
+pub fn reset_state_provider() {
+    *STATE_PROVIDER.lock().unwrap() = None;
+}
+
+/// Looks up a stored answer of the prompt with the global state provider.
+pub(crate) fn stored_answer(prompt_id: &Option<String>) -> Option<String> {
+    let prompt_id = prompt_id.as_ref()?;
+    STATE_PROVIDER.lock().unwrap().as_ref()?.get(prompt_id)
+}
+
+/// Runs the closure with the given global state provider, serializing
+/// the tests which set it.
+#[cfg(test)]
+pub(crate) fn with_state_provider<R>(
+    provider: impl StateProvider + Send + Sync + 'static,
+    f: impl FnOnce() -> R,
+) -> R {
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    let _lock = LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    set_state_provider(provider);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+    reset_state_provider();
+    result.unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+}
+
+#[cfg(test)]
+mod tests {
+    use console::Key;
+
+    use super::*;
+    use crate::prompt::interaction::PromptInteraction;
+    use crate::{Autocomplete, Confirm, Input, MultiSelect, MultilineInput, Number, Select};
+
+    fn stored(id: &str) -> Option<String> {
+        match id {
+            "name" => Some("my-app".to_string()),
+            "git" => Some("false".to_string()),
+            "lang" => Some("Rust".to_string()),
+            "tools" => Some("ESLint, Prettier".to_string()),
+            "port" => Some("8080".to_string()),
+            "message" => Some("Fix\n\nDetails".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn input_defaults_to_the_stored_answer() {
+        let name = with_state_provider(stored, || {
+            let mut input = Input::new("Name").prompt_id("name");
+            PromptInteraction::<String>::init(&mut input);
+            PromptInteraction::<String>::handle_key(&mut input, Key::Enter).submitted()
+        });
+        assert_eq!(name.as_deref(), Some("my-app"));
+    }
+
+    #[test]
+    fn input_default_value_wins_over_the_stored_answer() {
+        let name = with_state_provider(stored, || {
+            let mut input = Input::new("Name").prompt_id("name").default_value("app");
+            PromptInteraction::<String>::init(&mut input);
+            PromptInteraction::<String>::handle_key(&mut input, Key::Enter).submitted()
+        });
+        assert_eq!(name.as_deref(), Some("app"));
+    }
+
+    #[test]
+    fn confirm_starts_with_the_stored_answer() {
+        let git = with_state_provider(stored, || {
+            let mut confirm = Confirm::new("Git?").initial_value(true).prompt_id("git");
+            confirm.init();
+            confirm.handle_key(Key::Enter).submitted()
+        });
+        assert_eq!(git, Some(false));
+    }
+
+    #[test]
+    fn select_starts_at_the_stored_label() {
+        let lang = with_state_provider(stored, || {
+            let mut select = Select::new("Language")
+                .item("ts", "TypeScript", "")
+                .item("rs", "Rust", "")
+                .prompt_id("lang");
+            select.init();
+            select.handle_key(Key::Enter).submitted()
+        });
+        assert_eq!(lang, Some("rs"));
+    }
+
+    #[test]
+    fn multiselect_selects_the_stored_labels() {
+        let tools = with_state_provider(stored, || {
+            let mut multiselect = MultiSelect::new("Tools")
+                .item("eslint", "ESLint", "")
+                .item("gh", "GitHub Actions", "")
+                .item("prettier", "Prettier", "")
+                .prompt_id("tools");
+            multiselect.init();
+            multiselect.handle_key(Key::Enter).submitted()
+        });
+        assert_eq!(tools, Some(vec!["eslint", "prettier"]));
+    }
+
+    #[test]
+    fn number_defaults_to_the_stored_answer() {
+        let port = with_state_provider(stored, || {
+            let mut number = Number::<u16>::new("Port").prompt_id("port");
+            number.init();
+            number.handle_key(Key::Enter).submitted()
+        });
+        assert_eq!(port, Some(8080));
+    }
+
+    #[test]
+    fn autocomplete_types_the_stored_answer() {
+        let name = with_state_provider(stored, || {
+            let mut autocomplete = Autocomplete::new("Name").prompt_id("name");
+            autocomplete.init();
+            autocomplete.handle_key(Key::Enter).submitted()
+        });
+        assert_eq!(name.as_deref(), Some("my-app"));
+    }
+
+    #[test]
+    fn multiline_input_starts_with_the_stored_answer() {
+        let submit = |input: MultilineInput| {
+            with_state_provider(stored, || {
+                let mut input = input.prompt_id("message");
+                input.init();
+                input.handle_key(Key::Char('!'));
+                input.handle_key(Key::Char('\u{4}')).submitted()
+            })
+        };
+        assert_eq!(
+            submit(MultilineInput::new("Message")).as_deref(),
+            Some("Fix\n\nDetails!"),
+            "the cursor is at the end of the stored text"
+        );
+        assert_eq!(
+            submit(MultilineInput::new("Message").default_input("Draft")).as_deref(),
+            Some("Draft!")
+        );
+    }
+
+    #[test]
+    fn unknown_prompt_id_keeps_the_defaults() {
+        let port = with_state_provider(stored, || {
+            let mut number = Number::new("Port").default_value(3000).prompt_id("other");
+            number.init();
+            number.handle_key(Key::Enter).submitted()
+        });
+        assert_eq!(port, Some(3000));
+    }
+}
@@ -1,9 +1,33 @@
+use std::fmt::{self, Display, Formatter};
+use std::sync::atomic::{AtomicU8, Ordering};
 use std::sync::Mutex;
+use std::time::Duration;
 
-use console::{style, Emoji, Style};
+use console::{measure_text_width, style, Style};
 use once_cell::sync::Lazy;
 
+use crate::password::PasswordStrength;
 use crate::prompt::{cursor::StringCursor, interaction::State};
+use crate::SelectStyle;
+
+/// Whether the Unicode symbols are forced: `0` detects the terminal
+/// support, `1` forces Unicode, `2` forces the ASCII fallback.
+pub(crate) static UNICODE_SYMBOLS: AtomicU8 = AtomicU8::new(0);
+
+/// A symbol with the ASCII fallback (like [`console::Emoji`]) which respects
+/// [`set_unicode_symbols`](crate::set_unicode_symbols).
+#[derive(Clone, Copy)]
+struct Emoji<'a, 'b>(&'a str, &'b str);
+
+impl Display for Emoji<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match UNICODE_SYMBOLS.load(Ordering::Relaxed) {
+            1 => f.write_str(self.0),
+            2 => f.write_str(self.1),
+            _ => console::Emoji(self.0, self.1).fmt(f),
+        }
+    }
+}
 
 const S_STEP_ACTIVE: Emoji = Emoji("◆", "*");
 const S_STEP_CANCEL: Emoji = Emoji("■", "x");
@@ -20,6 +44,8 @@ const S_CHECKBOX_ACTIVE: Emoji = Emoji("◻", "[•]");
 const S_CHECKBOX_SELECTED: Emoji = Emoji("◼", "[+]");
 const S_CHECKBOX_INACTIVE: Emoji = Emoji("◻", "[ ]");
 const S_PASSWORD_MASK: Emoji = Emoji("▪", "•");
+const S_STRENGTH_FULL: Emoji = Emoji("▰", "#");
+const S_STRENGTH_EMPTY: Emoji = Emoji("▱", "-");
 
 const S_BAR_H: Emoji = Emoji("─", "-");
 const S_CORNER_TOP_RIGHT: Emoji = Emoji("╮", "+");
@@ -29,6 +55,18 @@ const S_CORNER_BOTTOM_RIGHT: Emoji = Emoji("╯", "+");
 const S_INFO: Emoji = Emoji("●", "•");
 const S_WARN: Emoji = Emoji("▲", "!");
 const S_ERROR: Emoji = Emoji("■", "x");
+const S_SKIP: Emoji = Emoji("◌", "-");
+
+const S_STATUS_SUCCESS: Emoji = Emoji("✔", "v");
+const S_STATUS_FAILURE: Emoji = Emoji("✖", "x");
+
+const S_ARROW_UP: Emoji = Emoji("↑", "^");
+const S_ARROW_DOWN: Emoji = Emoji("↓", "v");
+
+const S_ELLIPSIS: Emoji = Emoji("…", "...");
+
+const S_PROGRESS_FILL: Emoji = Emoji("█", "#");
+const S_PROGRESS_EMPTY: Emoji = Emoji("░", "-");
 
 const S_SPINNER: Emoji = Emoji("◒◐◓◑", "•oO0");
 
@@ -55,10 +93,41 @@ impl<T> From<&State<T>> for ThemeState {
     }
 }
 
+/// An item of the select list passed to [`Theme::format_select_list`].
+#[derive(Clone, Debug)]
+pub struct SelectListItem {
+    /// The label, styled and padded to align the hints.
+    pub label: String,
+    /// The hint shown next to the active item.
+    pub hint: String,
+    /// Whether the item can't be selected.
+    pub disabled: bool,
+}
+
+/// The layout of the select list passed to [`Theme::format_select_list`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct SelectListLayout {
+    /// The glyphs of the items.
+    pub style: SelectStyle,
+    /// The number of the grid columns, `1` for a plain list.
+    pub columns: usize,
+    /// The number of the items scrolled out above the visible ones.
+    pub hidden_above: usize,
+    /// The number of the items scrolled out below the visible ones.
+    pub hidden_below: usize,
+    /// The number of the empty lines between the items or the grid rows.
+    pub spacing: usize,
+    /// The width available for the rendering.
+    pub width: usize,
+}
+
 /// Defines rendering of the visual elements. By default, it implements the
 /// original [@Clack/prompts](https://www.npmjs.com/package/@clack/prompts) theme.
 ///
 /// ```
+/// # use cliclack::Theme;
+/// # struct ClackTheme;
 /// /// The default @clack/prompts theme is literally implemented like this.
 /// impl Theme for ClackTheme {}
 /// ```
@@ -67,6 +136,8 @@ impl<T> From<&State<T>> for ThemeState {
 /// the required methods:
 ///
 /// ```
+/// # use cliclack::{Theme, ThemeState};
+/// # use console::Style;
 /// struct MagentaTheme;
 ///
 /// impl Theme for MagentaTheme {
@@ -79,6 +150,9 @@ impl<T> From<&State<T>> for ThemeState {
 /// Then, set the theme with [`set_theme`] function.
 ///
 /// ```
+/// # use cliclack::{set_theme, Theme};
+/// # struct MagentaTheme;
+/// # impl Theme for MagentaTheme {}
 /// set_theme(MagentaTheme);
 /// ```
 ///
@@ -95,6 +169,15 @@ pub trait Theme {
         }
     }
 
+    /// Returns the color of the vertical side bar next to a list item,
+    /// `active` is set for the item under the cursor.
+    ///
+    /// Override it to highlight the bar of the active row, e.g. brighter.
+    /// Default: [`Theme::bar_color`] for all rows.
+    fn row_bar_color(&self, state: &ThemeState, _active: bool) -> Style {
+        self.bar_color(state)
+    }
+
     /// Returns the color of the symbol of the current rendering state.
     fn state_symbol_color(&self, state: &ThemeState) -> Style {
         match state {
@@ -167,6 +250,31 @@ pub trait Theme {
         style(S_ERROR).red().to_string()
     }
 
+    /// Returns the symbol of the skipped step.
+    fn skip_symbol(&self) -> String {
+        style(S_SKIP).dim().to_string()
+    }
+
+    /// Returns the symbol put before the cancellation message in the footer,
+    /// none by default.
+    ///
+    /// ```
+    /// use cliclack::{set_theme, Theme};
+    ///
+    /// struct MyTheme;
+    ///
+    /// impl Theme for MyTheme {
+    ///     fn cancel_symbol(&self) -> String {
+    ///         console::style("■").red().to_string()
+    ///     }
+    /// }
+    ///
+    /// set_theme(MyTheme);
+    /// ```
+    fn cancel_symbol(&self) -> String {
+        String::new()
+    }
+
     /// Returns the symbol of the active step.
     fn active_symbol(&self) -> String {
         style(S_STEP_ACTIVE).green().to_string()
@@ -177,11 +285,25 @@ pub trait Theme {
         style(S_STEP_SUBMIT).green().to_string()
     }
 
+    /// Returns whether the submitted values are rendered dimmed. Default: `true`.
+    ///
+    /// Override it to keep the submitted prompts in full color.
+    fn dim_submitted(&self) -> bool {
+        true
+    }
+
+    /// Returns whether the blank spacer lines (like `│` after the intro
+    /// and between the prompts) are omitted for a tighter layout. Default: `false`.
+    fn compact(&self) -> bool {
+        false
+    }
+
     /// Returns the console style of the checkbox item.
     fn checkbox_style(&self, state: &ThemeState, selected: bool, active: bool) -> Style {
         match state {
             ThemeState::Cancel if selected => Style::new().dim().strikethrough(),
-            ThemeState::Submit if selected => Style::new().dim(),
+            ThemeState::Submit if selected && self.dim_submitted() => Style::new().dim(),
+            ThemeState::Submit if selected => Style::new(),
             _ if !active => Style::new().dim(),
             _ => Style::new(),
         }
@@ -191,7 +313,7 @@ pub trait Theme {
     fn input_style(&self, state: &ThemeState) -> Style {
         match state {
             ThemeState::Cancel => Style::new().dim().strikethrough(),
-            ThemeState::Submit => Style::new().dim(),
+            ThemeState::Submit if self.dim_submitted() => Style::new().dim(),
             _ => Style::new(),
         }
     }
@@ -206,8 +328,18 @@ pub trait Theme {
 
     /// Highlights the cursor character in the input text formatting the whole
     /// string with the given style.
+    ///
+    /// The whole text is highlighted if it's selected.
     fn cursor_with_style(&self, cursor: &StringCursor, new_style: &Style) -> String {
-        let (left, cursor, right) = cursor.split();
+        if cursor.is_selected() {
+            return style(cursor).reverse().to_string();
+        }
+
+        let at_end = cursor.current().is_none();
+        let (left, mut cursor, right) = cursor.split();
+        if at_end {
+            cursor = self.cursor_end_glyph();
+        }
         format!(
             "{left}{cursor}{right}",
             left = new_style.apply_to(left),
@@ -216,6 +348,28 @@ pub trait Theme {
         )
     }
 
+    /// Returns the highlighted character of the cursor at the end of the text,
+    /// where there's no character under it. Default: a space.
+    ///
+    /// Override it if a reversed space is invisible on the terminal, e.g. with `_`.
+    fn cursor_end_glyph(&self) -> String {
+        " ".into()
+    }
+
+    /// Returns the status icon appended to a submitted value, see
+    /// [`Input::finalize`](crate::Input::finalize).
+    fn format_status(&self, success: bool) -> String {
+        match success {
+            true => style(S_STATUS_SUCCESS).green().to_string(),
+            false => style(S_STATUS_FAILURE).red().to_string(),
+        }
+    }
+
+    /// Returns the overflow indicator which is used wherever the text is truncated.
+    fn ellipsis(&self) -> String {
+        S_ELLIPSIS.to_string()
+    }
+
     /// Returns the password mask character.
     fn password_mask(&self) -> char {
         S_PASSWORD_MASK.to_string().chars().next().unwrap()
@@ -224,10 +378,33 @@ pub trait Theme {
     /// Formats the intro message (like `┌  title`).
     fn format_intro(&self, title: &str) -> String {
         let color = self.bar_color(&ThemeState::Submit);
+        let spacer = match self.compact() {
+            true => String::new(),
+            false => format!("{bar}\n", bar = color.apply_to(S_BAR)),
+        };
         format!(
-            "{start_bar}  {title}\n{bar}\n",
+            "{start_bar}  {title}\n{spacer}",
+            start_bar = color.apply_to(S_BAR_START),
+        )
+    }
+
+    /// Formats the intro message with an icon before the title (like `┌  🚀 title`).
+    fn format_intro_with_icon(&self, icon: &str, title: &str) -> String {
+        self.format_intro(&format!("{icon} {title}"))
+    }
+
+    /// Formats the intro message with a dimmed subtitle line under the title.
+    fn format_intro_with_subtitle(&self, title: &str, subtitle: &str) -> String {
+        let color = self.bar_color(&ThemeState::Submit);
+        let spacer = match self.compact() {
+            true => String::new(),
+            false => format!("{bar}\n", bar = color.apply_to(S_BAR)),
+        };
+        format!(
+            "{start_bar}  {title}\n{bar}  {subtitle}\n{spacer}",
             start_bar = color.apply_to(S_BAR_START),
             bar = color.apply_to(S_BAR),
+            subtitle = style(subtitle).dim(),
         )
     }
 
@@ -253,25 +430,124 @@ pub trait Theme {
 
     /// Formats the header of the prompt (like `◇  Input data`).
     fn format_header(&self, state: &ThemeState, prompt: &str) -> String {
-        format!(
-            "{state_symbol}  {prompt}\n",
+        self.format_header_inline(state, prompt) + "\n"
+    }
+
+    /// Formats the header of the prompt without the trailing newline,
+    /// so more content can be appended to the line.
+    ///
+    /// The lines of a multiline prompt after the first one are aligned
+    /// with the frame bar instead of the state symbol.
+    ///
+    /// ```
+    /// use cliclack::{Theme, ThemeState};
+    ///
+    /// struct MyTheme;
+    /// impl Theme for MyTheme {}
+    ///
+    /// let header = MyTheme.format_header_inline(&ThemeState::Active, "Name\n(used in logs)");
+    /// let lines = header.lines().collect::<Vec<_>>();
+    /// assert_eq!(lines.len(), 2);
+    /// assert!(lines[1].ends_with("  (used in logs)") && !lines[1].starts_with(' '));
+    /// ```
+    fn format_header_inline(&self, state: &ThemeState, prompt: &str) -> String {
+        let mut lines = prompt.split('\n');
+        let first = lines.next().unwrap_or_default();
+
+        let mut header = format!(
+            "{state_symbol}  {first}",
             state_symbol = self.state_symbol(state)
-        )
+        );
+        for line in lines {
+            header.push_str(&format!(
+                "\n{bar}  {line}",
+                bar = self.bar_color(state).apply_to(S_BAR)
+            ));
+        }
+        header
     }
 
     /// Formats the footer of the prompt (like `└  Operation cancelled.`).
     fn format_footer(&self, state: &ThemeState) -> String {
+        if let ThemeState::Submit = state {
+            if self.compact() {
+                return String::new();
+            }
+        }
+
         format!(
             "{}\n", // '\n' vanishes by style applying, thus exclude it from styling
             self.bar_color(state).apply_to(match state {
                 ThemeState::Active => format!("{S_BAR_END}"),
-                ThemeState::Cancel => format!("{S_BAR_END}  Operation cancelled."),
+                ThemeState::Cancel => match self.cancel_symbol() {
+                    symbol if symbol.is_empty() => format!("{S_BAR_END}  Operation cancelled."),
+                    symbol => return self.format_footer_cancel(&symbol),
+                },
                 ThemeState::Submit => format!("{S_BAR}"),
-                ThemeState::Error(err) => format!("{S_BAR_END}  {err}"),
+                ThemeState::Error(err) => return self.format_footer_error(err),
             })
         )
     }
 
+    /// Formats the footer of the cancelled prompt with the cancellation
+    /// symbol (like `└  ■ Operation cancelled.`).
+    fn format_footer_cancel(&self, symbol: &str) -> String {
+        let state = ThemeState::Cancel;
+        format!(
+            "{bar_end}  {symbol} {message}\n",
+            bar_end = self.bar_color(&state).apply_to(S_BAR_END),
+            message = self.bar_color(&state).apply_to("Operation cancelled."),
+        )
+    }
+
+    /// Formats the footer of the prompt with a validation error
+    /// (like `└  ■ Value is required`).
+    ///
+    /// It's distinct from [`Theme::format_footer_warning`] by the symbol and color.
+    fn format_footer_error(&self, err: &str) -> String {
+        let state = ThemeState::Error(err.into());
+        format!(
+            "{bar_end}  {symbol} {err}\n",
+            bar_end = self.bar_color(&state).apply_to(S_BAR_END),
+            symbol = self.error_symbol(),
+            err = self.bar_color(&state).apply_to(err),
+        )
+    }
+
+    /// Formats the validation error rendered on the input line instead of
+    /// the footer (like `│  abc  Must be a number`), see
+    /// [`Input::inline_error`](crate::Input::inline_error).
+    fn format_inline_error(&self, err: &str) -> String {
+        style(format!("  {err}")).red().to_string()
+    }
+
+    /// Formats the footer of the prompt with a validation error rendered
+    /// inline, so the footer keeps its line (like `└`).
+    fn format_footer_inline_error(&self, err: &str) -> String {
+        let state = ThemeState::Error(err.into());
+        format!("{}\n", self.bar_color(&state).apply_to(S_BAR_END))
+    }
+
+    /// Formats the footer of the prompt with a validation warning
+    /// (like `└  ▲ Value looks suspicious`), see
+    /// [`Input::validate_warning`](crate::Input::validate_warning).
+    ///
+    /// It's distinct from [`Theme::format_footer_error`] by the symbol and color.
+    fn format_footer_warning(&self, warning: &str) -> String {
+        let state = ThemeState::Error(warning.into());
+        format!(
+            "{bar_end}  {symbol} {warning}\n",
+            bar_end = self.bar_color(&state).apply_to(S_BAR_END),
+            symbol = self.warning_symbol(),
+            warning = style(warning).yellow(),
+        )
+    }
+
+    /// Formats an empty line with the frame bar only (like `│`).
+    fn format_empty_line(&self, state: &ThemeState) -> String {
+        format!("{bar}\n", bar = self.bar_color(state).apply_to(S_BAR))
+    }
+
     /// Formats the input cursor with the given style adding frame bars around.
     ///
     /// It hides the cursor when the input is not active.
@@ -289,6 +565,101 @@ pub trait Theme {
         )
     }
 
+    /// Formats the multiline input drawing each line with the frame bar,
+    /// the cursor is highlighted on its line and column.
+    ///
+    /// It hides the cursor when the input is not active.
+    fn format_multiline_input(&self, state: &ThemeState, cursor: &StringCursor) -> String {
+        let new_style = &self.input_style(state);
+        let bar = self.bar_color(state).apply_to(S_BAR);
+
+        let lines = match state {
+            ThemeState::Active | ThemeState::Error(_) => {
+                let (left, current, right) = cursor.split();
+                // The cursor at the end of a line stands on the newline.
+                let (current, right) = match current.as_str() {
+                    "" => (self.cursor_end_glyph(), right),
+                    "\n" => (self.cursor_end_glyph(), format!("\n{right}")),
+                    _ => (current, right),
+                };
+
+                let (before, line_left) = left.rsplit_once('\n').unwrap_or(("", &left));
+                let (line_right, after) = right.split_once('\n').unwrap_or((&right, ""));
+
+                let mut lines = Vec::new();
+                if left.contains('\n') {
+                    lines.extend(
+                        before
+                            .split('\n')
+                            .map(|line| new_style.apply_to(line).to_string()),
+                    );
+                }
+                lines.push(format!(
+                    "{left}{cursor}{right}",
+                    left = new_style.apply_to(line_left),
+                    cursor = style(current).reverse(),
+                    right = new_style.apply_to(line_right),
+                ));
+                if right.contains('\n') {
+                    lines.extend(
+                        after
+                            .split('\n')
+                            .map(|line| new_style.apply_to(line).to_string()),
+                    );
+                }
+                lines
+            }
+            _ => cursor
+                .to_string()
+                .split('\n')
+                .map(|line| new_style.apply_to(line).to_string())
+                .collect(),
+        };
+
+        lines
+            .iter()
+            .map(|line| format!("{bar}  {line}\n"))
+            .collect()
+    }
+
+    /// Formats the password strength meter under the input (like `│  ▰▰▱ Medium`).
+    ///
+    /// It's hidden on the submit and cancel states.
+    fn format_password_strength(&self, state: &ThemeState, strength: PasswordStrength) -> String {
+        let (level, label, color) = match strength {
+            PasswordStrength::Weak => (1, "Weak", Style::new().red()),
+            PasswordStrength::Medium => (2, "Medium", Style::new().yellow()),
+            PasswordStrength::Strong => (3, "Strong", Style::new().green()),
+        };
+
+        match state {
+            ThemeState::Active | ThemeState::Error(_) => format!(
+                "{bar}  {full}{empty} {label}\n",
+                bar = self.bar_color(state).apply_to(S_BAR),
+                full = color.apply_to(S_STRENGTH_FULL.to_string().repeat(level)),
+                empty = style(S_STRENGTH_EMPTY.to_string().repeat(3 - level)).dim(),
+                label = color.apply_to(label),
+            ),
+            _ => String::new(),
+        }
+    }
+
+    /// Formats the masked first entry of the password and the header of its
+    /// confirmation below it, while the confirmation is typed
+    /// (like `│  ******` and `│  Confirm password`).
+    fn format_password_confirm_header(
+        &self,
+        state: &ThemeState,
+        masked: &str,
+        prompt: &str,
+    ) -> String {
+        let bar = self.bar_color(state).apply_to(S_BAR);
+        format!(
+            "{bar}  {masked}\n{bar}  {prompt}\n",
+            masked = self.placeholder_style(state).apply_to(masked),
+        )
+    }
+
     /// Formats the input cursor with the dimmed style of placeholder.
     ///
     /// Additionally:
@@ -310,6 +681,29 @@ pub trait Theme {
         )
     }
 
+    /// Formats the default value shown while the input is empty, distinct
+    /// from the placeholder (like `│  8080 (default)`).
+    ///
+    /// * Hides the default value at the cancelled state.
+    /// * The submitted default value is drawn with [`Theme::format_input`].
+    fn format_default_value(&self, state: &ThemeState, cursor: &StringCursor) -> String {
+        let new_style = &self.placeholder_style(state).italic();
+
+        let default = &match state {
+            ThemeState::Active | ThemeState::Error(_) => {
+                let suffix = self.placeholder_style(state).apply_to("(default)");
+                format!("{} {suffix}", self.cursor_with_style(cursor, new_style))
+            }
+            ThemeState::Cancel => "".to_string(),
+            _ => new_style.apply_to(cursor).to_string(),
+        };
+
+        format!(
+            "{bar}  {default}\n",
+            bar = self.bar_color(state).apply_to(S_BAR)
+        )
+    }
+
     /// Returns the radio item without frame bars around the item.
     ///
     /// The radio item is used in the selection list and in the confirmation prompt.
@@ -348,9 +742,50 @@ pub trait Theme {
         )
     }
 
+    /// Returns the disabled item of the select and multiselect lists without
+    /// frame bars around, with the label dimmed and struck through.
+    ///
+    /// The hint is always shown, e.g. to tell why the item is unavailable.
+    /// Hides the item on the submit and cancel states.
+    fn disabled_item(&self, state: &ThemeState, label: &str, hint: &str) -> String {
+        match state {
+            ThemeState::Cancel | ThemeState::Submit => return String::new(),
+            _ => {}
+        }
+
+        let symbol = style(S_SKIP).dim();
+        let label = style(label).dim().strikethrough();
+        let hint = match hint {
+            "" => String::new(),
+            hint => format!(
+                " {}",
+                self.placeholder_style(state).apply_to(format!("({hint})"))
+            ),
+        };
+
+        format!("{symbol} {label}{hint}")
+    }
+
+    /// Returns the full disabled list item formatting with frame bars around.
+    ///
+    /// Hides the item on the submit and cancel states.
+    fn format_disabled_item(&self, state: &ThemeState, label: &str, hint: &str) -> String {
+        match state {
+            ThemeState::Cancel | ThemeState::Submit => return String::new(),
+            _ => {}
+        }
+
+        format!(
+            "{bar}  {item}\n",
+            bar = self.bar_color(state).apply_to(S_BAR),
+            item = self.disabled_item(state, label, hint),
+        )
+    }
+
     /// Returns the full select list item formatting with frame bars around.
     ///
     /// Hides the item if not selected on the submit and cancel states.
+    /// The submitted item is rendered with its human-readable label.
     fn format_select_item(
         &self,
         state: &ThemeState,
@@ -365,11 +800,223 @@ pub trait Theme {
 
         format!(
             "{bar}  {radio_item}\n",
-            bar = self.bar_color(state).apply_to(S_BAR),
+            bar = self.row_bar_color(state, selected).apply_to(S_BAR),
             radio_item = self.radio_item(state, selected, label, hint)
         )
     }
 
+    /// Formats a suggestion of the autocomplete prompt under the input,
+    /// `active` is set for the highlighted one.
+    ///
+    /// It's hidden on the submit and cancel states.
+    fn format_autocomplete_item(
+        &self,
+        state: &ThemeState,
+        active: bool,
+        suggestion: &str,
+    ) -> String {
+        match state {
+            ThemeState::Active | ThemeState::Error(_) => format!(
+                "{bar}  {item}\n",
+                bar = self.row_bar_color(state, active).apply_to(S_BAR),
+                item = self.radio_item(&ThemeState::Active, active, suggestion, ""),
+            ),
+            _ => String::new(),
+        }
+    }
+
+    /// Formats the description of the active select item under the list,
+    /// `None` shows a loading placeholder (like `│  Loading…`).
+    ///
+    /// It's hidden on the submit and cancel states.
+    fn format_select_description(&self, state: &ThemeState, description: Option<&str>) -> String {
+        let text = match description {
+            Some(description) => description.to_string(),
+            None => format!("Loading{}", self.ellipsis()),
+        };
+
+        match state {
+            ThemeState::Active | ThemeState::Error(_) => format!(
+                "{bar}\n{bar}  {text}\n",
+                bar = self.bar_color(state).apply_to(S_BAR),
+                text = self.placeholder_style(state).apply_to(text),
+            ),
+            _ => String::new(),
+        }
+    }
+
+    /// Formats a continuation line of the wrapped hint of the active item
+    /// indented under its label (like `│    long hint)`).
+    fn format_hint_line(&self, state: &ThemeState, indent: usize, line: &str) -> String {
+        format!(
+            "{bar}  {indent}{line}\n",
+            bar = self.bar_color(state).apply_to(S_BAR),
+            indent = " ".repeat(indent),
+            line = self.placeholder_style(state).apply_to(line),
+        )
+    }
+
+    /// Returns the lines above and below the scrolled select list with
+    /// the numbers of the hidden items (like `│  ↑ 3 more`), an empty line
+    /// if there are none.
+    fn format_select_overflow(
+        &self,
+        state: &ThemeState,
+        above: usize,
+        below: usize,
+    ) -> (String, String) {
+        let line = |symbol: Emoji, hidden: usize| match hidden {
+            0 => String::new(),
+            _ => format!(
+                "{bar}  {more}\n",
+                bar = self.bar_color(state).apply_to(S_BAR),
+                more = self
+                    .placeholder_style(state)
+                    .apply_to(format!("{symbol} {hidden} more")),
+            ),
+        };
+
+        (line(S_ARROW_UP, above), line(S_ARROW_DOWN, below))
+    }
+
+    /// Formats the whole select list from its visible items, where `active`
+    /// is the index of the highlighted item among them.
+    ///
+    /// By default, the items are formatted with [`Theme::format_select_item`]
+    /// (or [`Theme::format_multiselect_item`] for the checkbox style) and
+    /// [`Theme::format_disabled_item`], the overflowing hint of the active
+    /// item is wrapped under it with [`Theme::format_hint_line`], and the
+    /// overflow indicators and the spacing lines are added. In the grid layout,
+    /// the items are formatted with [`Theme::radio_item`] into the rows of
+    /// [`Theme::format_select_row`].
+    ///
+    /// Override it to re-lay out the list, e.g. to number the items.
+    ///
+    /// ```
+    /// use cliclack::{SelectListItem, SelectListLayout, Theme, ThemeState};
+    ///
+    /// struct NumberedTheme;
+    /// impl Theme for NumberedTheme {
+    ///     fn format_select_list(
+    ///         &self,
+    ///         _state: &ThemeState,
+    ///         items: &[SelectListItem],
+    ///         active: usize,
+    ///         layout: &SelectListLayout,
+    ///     ) -> String {
+    ///         let mut list = String::new();
+    ///         for (i, item) in items.iter().enumerate() {
+    ///             let marker = if i == active { ">" } else { " " };
+    ///             let number = layout.hidden_above + i + 1;
+    ///             list.push_str(&format!("│ {marker} {number}. {}\n", item.label));
+    ///         }
+    ///         list
+    ///     }
+    /// }
+    /// ```
+    fn format_select_list(
+        &self,
+        state: &ThemeState,
+        items: &[SelectListItem],
+        active: usize,
+        layout: &SelectListLayout,
+    ) -> String {
+        let gap = self.format_empty_line(state).repeat(layout.spacing);
+
+        if layout.columns > 1 {
+            let cells = items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| match layout.style {
+                    _ if item.disabled => self.disabled_item(state, &item.label, ""),
+                    SelectStyle::Radio => self.radio_item(state, i == active, &item.label, ""),
+                    SelectStyle::Checkbox => {
+                        self.checkbox_item(state, i == active, i == active, &item.label, "")
+                    }
+                })
+                .collect::<Vec<_>>();
+            let cell_width = cells.iter().map(|cell| measure_text_width(cell)).max();
+
+            let rows = cells
+                .chunks(layout.columns)
+                .map(|chunk| {
+                    let padded = chunk
+                        .iter()
+                        .map(|cell| {
+                            let padding = cell_width.unwrap_or(0) - measure_text_width(cell);
+                            format!("{cell}{}", " ".repeat(padding))
+                        })
+                        .collect::<Vec<_>>();
+                    self.format_select_row(state, &padded)
+                })
+                .collect::<Vec<_>>();
+            return rows.join(&gap);
+        }
+
+        let format_item = |active: bool, label: &str, hint: &str| match layout.style {
+            SelectStyle::Radio => self.format_select_item(state, active, label, hint),
+            SelectStyle::Checkbox => {
+                self.format_multiselect_item(state, active, active, label, hint)
+            }
+        };
+        let wrap_hints = matches!(state, ThemeState::Active | ThemeState::Error(_));
+
+        let rows = items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                if item.disabled {
+                    return self.format_disabled_item(state, &item.label, &item.hint);
+                }
+                let active = i == active;
+                let row = format_item(active, &item.label, &item.hint);
+                if !active
+                    || !wrap_hints
+                    || measure_text_width(row.trim_end_matches('\n')) <= layout.width
+                {
+                    return row;
+                }
+
+                // Wraps the overflowing hint of the active item under the label.
+                let mut row = format_item(active, &item.label, "");
+                let indent = measure_text_width(&match layout.style {
+                    SelectStyle::Radio => self.radio_item(state, true, "", ""),
+                    SelectStyle::Checkbox => self.checkbox_item(state, true, true, "", ""),
+                });
+                let hint = format!("({})", item.hint);
+                let hint_width = layout.width.saturating_sub(3 + indent).max(1);
+                for line in textwrap::wrap(&hint, hint_width) {
+                    row.push_str(&self.format_hint_line(state, indent, &line));
+                }
+                row
+            })
+            .collect::<Vec<_>>();
+
+        let (above, below) =
+            self.format_select_overflow(state, layout.hidden_above, layout.hidden_below);
+        above + &rows.join(&gap) + &below
+    }
+
+    /// Returns a row of the select items laid out in a grid with frame bars around.
+    ///
+    /// The items are formatted with [`Theme::radio_item`] and padded to the same width.
+    fn format_select_row(&self, state: &ThemeState, items: &[String]) -> String {
+        format!(
+            "{bar}  {row}\n",
+            bar = self.bar_color(state).apply_to(S_BAR),
+            row = items.join("  ").trim_end(),
+        )
+    }
+
+    /// Returns the line rendered in place of the items when the select list is empty.
+    fn format_select_empty(&self, state: &ThemeState) -> String {
+        format!(
+            "{bar}  {message}\n",
+            bar = self.bar_color(state).apply_to(S_BAR),
+            message = self.placeholder_style(state).apply_to("No matches"),
+        )
+    }
+
     /// Returns the checkbox item without frame bars around the item.
     ///
     /// Hides the item if not selected on the submit and cancel states.
@@ -423,24 +1070,57 @@ pub trait Theme {
 
         format!(
             "{bar}  {checkbox_item}\n",
-            bar = self.bar_color(state).apply_to(S_BAR),
+            bar = self.row_bar_color(state, active).apply_to(S_BAR),
             checkbox_item = self.checkbox_item(state, selected, active, label, hint),
         )
     }
 
+    /// Returns the divider between the options of the confirmation prompt
+    /// (like ` / `), it's shown only while the prompt is active.
+    fn format_confirm_divider(&self, state: &ThemeState) -> String {
+        match state {
+            ThemeState::Active => self.placeholder_style(state).apply_to(" / ").to_string(),
+            _ => "".to_string(),
+        }
+    }
+
+    /// Formats the running total of the selected multiselect items appended
+    /// to the header (like ` (total: 42)`).
+    ///
+    /// It's hidden on the cancel state.
+    fn format_multiselect_total(&self, state: &ThemeState, total: i64) -> String {
+        match state {
+            ThemeState::Cancel => String::new(),
+            _ => format!(
+                " {}",
+                self.placeholder_style(state)
+                    .apply_to(format!("(total: {total})"))
+            ),
+        }
+    }
+
+    /// Formats the submitted multiselect items joined on a single line.
+    fn format_multiselect_summary(&self, state: &ThemeState, summary: &str) -> String {
+        format!(
+            "{bar}  {summary}\n",
+            bar = self.bar_color(state).apply_to(S_BAR),
+            summary = self.input_style(state).apply_to(summary),
+        )
+    }
+
     /// Returns the full confirmation prompt rendering.
-    fn format_confirm(&self, state: &ThemeState, confirm: bool) -> String {
+    ///
+    /// The "No" option is rendered first if `no_first` is set.
+    fn format_confirm(&self, state: &ThemeState, confirm: bool, no_first: bool) -> String {
         let yes = self.radio_item(state, confirm, "Yes", "");
         let no = self.radio_item(state, !confirm, "No", "");
 
-        let inactive_style = &self.placeholder_style(state);
-        let divider = match state {
-            ThemeState::Active => inactive_style.apply_to(" / ").to_string(),
-            _ => "".to_string(),
-        };
+        let divider = self.format_confirm_divider(state);
+
+        let (first, second) = if no_first { (no, yes) } else { (yes, no) };
 
         format!(
-            "{bar}  {yes}{divider}{no}\n",
+            "{bar}  {first}{divider}{second}\n",
             bar = self.bar_color(state).apply_to(S_BAR),
         )
     }
@@ -450,35 +1130,166 @@ pub trait Theme {
         "{spinner:.magenta}  {msg}".into()
     }
 
+    /// Returns the frame bar prefixing the spinner line (like `│  `).
+    ///
+    /// It's a literal text of the [`indicatif::ProgressBar`] template,
+    /// thus, it must not contain curly braces.
+    fn format_spinner_gutter(&self) -> String {
+        format!(
+            "{bar}  ",
+            bar = self.bar_color(&ThemeState::Submit).apply_to(S_BAR)
+        )
+    }
+
     /// Returns the spinner stop style as a final message.
     ///
     /// It's not symmetric to [`Theme::format_spinner_start`] because of a workaround
     /// for the [`indicatif::ProgressBar`] spinner behavior which disrupts
     /// the line after the stop message reproduced while terminal resizing
     /// (see [`Spinner::stop`](fn@crate::Spinner::stop)).
-    fn format_spinner_stop(&self, msg: &str) -> String {
+    ///
+    /// The state tells a success ([`ThemeState::Submit`]) apart from
+    /// a failure ([`ThemeState::Error`]) and a cancellation ([`ThemeState::Cancel`]).
+    /// A failure is colored with [`Theme::bar_color`] of the error state.
+    fn format_spinner_stop(&self, state: &ThemeState, msg: &str) -> String {
+        let (symbol, msg) = match state {
+            ThemeState::Error(_) => (
+                self.state_symbol(state),
+                self.bar_color(state).apply_to(msg).to_string(),
+            ),
+            ThemeState::Cancel => (
+                self.state_symbol(state),
+                self.input_style(state).apply_to(msg).to_string(),
+            ),
+            _ => (self.state_symbol(&ThemeState::Submit), msg.to_string()),
+        };
+
         format!(
             "{symbol}  {msg}\n{bar}",
+            bar = self.bar_color(&ThemeState::Submit).apply_to(S_BAR)
+        )
+    }
+
+    /// Formats the duration of the task appended to the spinner stop message
+    /// compactly and dimmed (like `(3.2s)` or `(1m 5s)`), see
+    /// [`Spinner::with_timer`](fn@crate::Spinner::with_timer).
+    fn format_spinner_elapsed(&self, elapsed: Duration) -> String {
+        let secs = elapsed.as_secs();
+        let text = match secs {
+            0..=59 => format!("({:.1}s)", elapsed.as_secs_f64()),
+            60..=3599 => format!("({}m {}s)", secs / 60, secs % 60),
+            _ => format!("({}h {}m)", secs / 3600, secs % 3600 / 60),
+        };
+        style(text).dim().to_string()
+    }
+
+    /// Returns the spinner stop message with the frame bar only, without
+    /// the state symbol (like `│  {msg}`).
+    fn format_spinner_stop_plain(&self, msg: &str) -> String {
+        format!(
+            "{bar}  {msg}\n{bar}",
+            bar = self.bar_color(&ThemeState::Submit).apply_to(S_BAR)
+        )
+    }
+
+    /// Returns the frame bar prefixing the lines of a
+    /// [`MultiProgress`](crate::MultiProgress) group (like `│  `).
+    ///
+    /// It's a literal text of the [`indicatif::ProgressBar`] template,
+    /// thus, it must not contain curly braces.
+    fn format_multi_progress_gutter(&self) -> String {
+        format!(
+            "{bar}  ",
+            bar = self.bar_color(&ThemeState::Active).apply_to(S_BAR)
+        )
+    }
+
+    /// Returns the line of a stopped task in a [`MultiProgress`](crate::MultiProgress)
+    /// group which stays in place of the task (like `│  ◇  {msg}`).
+    ///
+    /// The state is the same as of [`Theme::format_spinner_stop`].
+    fn format_multi_progress_item_stop(&self, state: &ThemeState, msg: &str) -> String {
+        let stop = self.format_spinner_stop(state, msg);
+        let line = stop.lines().next().unwrap_or_default();
+        format!(
+            "{gutter}{line}",
+            gutter = self.format_multi_progress_gutter()
+        )
+    }
+
+    /// Returns the footer drawn under the stopped [`MultiProgress`](crate::MultiProgress)
+    /// group (like `◇  {msg}`).
+    fn format_multi_progress_stop(&self, msg: &str) -> String {
+        format!(
+            "{symbol}  {msg}\n{bar}\n",
             symbol = self.state_symbol(&ThemeState::Submit),
             bar = self.bar_color(&ThemeState::Submit).apply_to(S_BAR)
         )
     }
 
+    /// Returns the progress bar style for the [`indicatif::ProgressBar`].
+    ///
+    /// The bar is surrounded with [`Theme::progress_delimiters`].
+    fn format_progress_start(&self) -> String {
+        let (left, right) = self.progress_delimiters();
+        format!(
+            "{{spinner:.magenta}}  {{msg}} {left}{{bar:30.magenta}}{right} {{pos}}/{{len}} ({{percent}}%)"
+        )
+    }
+
+    /// Returns the progress bar stop message as a final line.
+    ///
+    /// Like [`Theme::format_spinner_stop`], it's printed above the cleared bar
+    /// as a workaround of the line disruption while terminal resizing.
+    fn format_progress_stop(&self, msg: &str) -> String {
+        format!(
+            "{symbol}  {msg}\n{bar}",
+            symbol = self.state_symbol(&ThemeState::Submit),
+            bar = self.bar_color(&ThemeState::Submit).apply_to(S_BAR)
+        )
+    }
+
+    /// Returns the character of the filled part of the progress bar.
+    fn progress_fill_char(&self) -> char {
+        S_PROGRESS_FILL.to_string().chars().next().unwrap()
+    }
+
+    /// Returns the character of the empty part of the progress bar.
+    fn progress_empty_char(&self) -> char {
+        S_PROGRESS_EMPTY.to_string().chars().next().unwrap()
+    }
+
+    /// Returns the delimiters around the progress bar, e.g. `[` and `]`.
+    /// Default: none.
+    ///
+    /// They are literal texts of the [`indicatif::ProgressBar`] template,
+    /// thus, they must not contain curly braces.
+    fn progress_delimiters(&self) -> (String, String) {
+        (String::new(), String::new())
+    }
+
     /// Returns the spinner character sequence.
     fn spinner_chars(&self) -> String {
         S_SPINNER.to_string()
     }
 
     /// Returns the multiline note message rendering.
-    fn format_note(&self, prompt: &str, message: &str) -> String {
-        let message = format!("\n{message}\n");
+    ///
+    /// The state defines the note chrome: [`ThemeState::Submit`] for a regular
+    /// note, [`ThemeState::Error`] for a warning, and [`ThemeState::Cancel`]
+    /// for an error.
+    fn format_note(&self, state: &ThemeState, prompt: &str, message: &str) -> String {
+        let message = match self.compact() {
+            true => message.to_string(),
+            false => format!("\n{message}\n"),
+        };
         let width = 2 + message
             .split('\n')
             .fold(0usize, |acc, line| line.chars().count().max(acc))
             .max(prompt.chars().count());
 
-        let symbol = self.state_symbol(&ThemeState::Submit);
-        let bar_color = self.bar_color(&ThemeState::Submit);
+        let symbol = self.state_symbol(state);
+        let bar_color = self.bar_color(state);
         let text_color = self.input_style(&ThemeState::Submit);
 
         let header = format!(
@@ -499,21 +1310,50 @@ pub trait Theme {
             })
             .collect::<String>();
 
-        let footer = bar_color
-            .apply_to(format!(
-                "{S_CONNECT_LEFT}{horizontal_bar}{S_CORNER_BOTTOM_RIGHT}\n{bar}\n",
+        // '\n' is excluded from styling, so the lines can be nested.
+        let spacer = match self.compact() {
+            true => String::new(),
+            false => format!("{}\n", bar_color.apply_to(S_BAR)),
+        };
+        let footer = format!(
+            "{}\n{spacer}",
+            bar_color.apply_to(format!(
+                "{S_CONNECT_LEFT}{horizontal_bar}{S_CORNER_BOTTOM_RIGHT}",
                 horizontal_bar = S_BAR_H.to_string().repeat(width + 3),
-                bar = bar_color.apply_to(S_BAR),
             ))
-            .to_string();
+        );
 
         header + &body + &footer
     }
 
+    /// Returns the note box nested into the outer frame bars of the given
+    /// level (like `│  ◇  Warning ─╮`), e.g. for a note of a sub-flow.
+    ///
+    /// The box is rendered with [`Theme::format_note`].
+    fn format_note_nested(
+        &self,
+        state: &ThemeState,
+        level: usize,
+        prompt: &str,
+        message: &str,
+    ) -> String {
+        let gutter = format!(
+            "{bar}  ",
+            bar = self.bar_color(&ThemeState::Submit).apply_to(S_BAR)
+        )
+        .repeat(level);
+
+        self.format_note(state, prompt, message)
+            .lines()
+            .map(|line| format!("{gutter}{line}\n"))
+            .collect()
+    }
+
     /// Returns a log message rendering with a chosen symbol.
     fn format_log(&self, text: &str, symbol: &str) -> String {
         let mut parts = vec![];
-        let mut lines = text.lines().chain("\n".lines());
+        let spacer = if self.compact() { "" } else { "\n" };
+        let mut lines = text.lines().chain(spacer.lines());
 
         if let Some(first) = lines.next() {
             parts.push(format!("{symbol}  {first}"));
@@ -537,6 +1377,111 @@ struct ClackTheme;
 /// Using default @clack/prompts theme implementation from the [`Theme`] trait.
 impl Theme for ClackTheme {}
 
+/// Colorblind-friendly theme.
+///
+/// Avoids the red/green distinction relying on the blue/orange palette
+/// and brightness instead, while the state symbols keep their distinct shapes.
+///
+/// ```
+/// use cliclack::{set_theme, ColorblindTheme};
+///
+/// set_theme(ColorblindTheme);
+/// ```
+pub struct ColorblindTheme;
+
+impl Theme for ColorblindTheme {
+    fn bar_color(&self, state: &ThemeState) -> Style {
+        match state {
+            ThemeState::Active => Style::new().blue().bright(),
+            ThemeState::Cancel => Style::new().color256(208),
+            ThemeState::Submit => Style::new().bright().black(),
+            ThemeState::Error(_) => Style::new().color256(214).bold(),
+        }
+    }
+
+    fn state_symbol_color(&self, state: &ThemeState) -> Style {
+        match state {
+            ThemeState::Submit => Style::new().blue(),
+            _ => self.bar_color(state),
+        }
+    }
+}
+
+/// The background color brightness of the terminal.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TerminalBackground {
+    /// A light background, e.g. white.
+    Light,
+    /// A dark background, e.g. black.
+    Dark,
+    /// The background couldn't be detected.
+    Unknown,
+}
+
+/// The background forced with [`set_terminal_background`]: `0` detects it,
+/// `1` is light, `2` is dark, `3` is unknown.
+static TERMINAL_BACKGROUND: AtomicU8 = AtomicU8::new(0);
+
+/// Returns the background of the terminal, e.g. for a theme to choose
+/// readable colors.
+///
+/// It's detected by the `COLORFGBG` environment variable (like `15;0`) which
+/// many terminals set, unless forced with [`set_terminal_background`].
+pub fn terminal_background() -> TerminalBackground {
+    match TERMINAL_BACKGROUND.load(Ordering::Relaxed) {
+        1 => return TerminalBackground::Light,
+        2 => return TerminalBackground::Dark,
+        3 => return TerminalBackground::Unknown,
+        _ => {}
+    }
+
+    let colorfgbg = std::env::var("COLORFGBG").unwrap_or_default();
+    match colorfgbg.rsplit(';').next().map(str::parse::<u8>) {
+        // The light ANSI colors: white, light gray, and the bright ones but black.
+        Some(Ok(7 | 9..=15)) => TerminalBackground::Light,
+        Some(Ok(_)) => TerminalBackground::Dark,
+        _ => TerminalBackground::Unknown,
+    }
+}
+
+/// Forces the result of [`terminal_background`] instead of the detection.
+pub fn set_terminal_background(background: TerminalBackground) {
+    let value = match background {
+        TerminalBackground::Light => 1,
+        TerminalBackground::Dark => 2,
+        TerminalBackground::Unknown => 3,
+    };
+    TERMINAL_BACKGROUND.store(value, Ordering::Relaxed);
+}
+
+/// A theme adapting the colors to the [`terminal_background`], e.g. with
+/// the darker shades readable on a light background.
+///
+/// ```
+/// use cliclack::{set_theme, AdaptiveTheme};
+///
+/// set_theme(AdaptiveTheme);
+/// ```
+pub struct AdaptiveTheme;
+
+impl Theme for AdaptiveTheme {
+    fn bar_color(&self, state: &ThemeState) -> Style {
+        match (state, terminal_background()) {
+            (ThemeState::Active, TerminalBackground::Light) => Style::new().blue(),
+            (ThemeState::Submit, TerminalBackground::Light) => Style::new().black(),
+            (ThemeState::Error(_), TerminalBackground::Light) => Style::new().color256(130),
+            _ => ClackTheme.bar_color(state),
+        }
+    }
+
+    fn state_symbol_color(&self, state: &ThemeState) -> Style {
+        match (state, terminal_background()) {
+            (ThemeState::Submit, TerminalBackground::Light) => Style::new().color256(28),
+            _ => ClackTheme.state_symbol_color(state),
+        }
+    }
+}
+
 /// The global theme instance (singleton).
 ///
 /// It can be set with [`set_theme`] function.
@@ -554,3 +1499,377 @@ pub fn set_theme<T: Theme + Sync + Send + 'static>(theme: T) {
 pub fn reset_theme() {
     *THEME.lock().unwrap() = Box::new(ClackTheme);
 }
+
+/// Runs the closure with the given global theme, serializing the tests
+/// which set it.
+#[cfg(test)]
+pub(crate) fn with_theme<R>(theme: impl Theme + Send + Sync + 'static, f: impl FnOnce() -> R) -> R {
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    let _lock = LOCK.lock().unwrap_or_else(|err| err.into_inner());
+    set_theme(theme);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+    reset_theme();
+    result.unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Forces the colors, the other tests compare the text without them.
+    fn colors() {
+        console::set_colors_enabled(true);
+    }
+
+    fn plain(text: &str) -> String {
+        console::strip_ansi_codes(text).into_owned()
+    }
+
+    #[test]
+    fn colorblind_theme_avoids_the_default_red_and_green() {
+        let (cancel, submit) = (ThemeState::Cancel, ThemeState::Submit);
+
+        assert_ne!(
+            ColorblindTheme.bar_color(&cancel),
+            ClackTheme.bar_color(&cancel)
+        );
+        assert_ne!(
+            ColorblindTheme.state_symbol_color(&cancel),
+            ClackTheme.state_symbol_color(&cancel)
+        );
+        assert_ne!(
+            ColorblindTheme.state_symbol_color(&submit),
+            ClackTheme.state_symbol_color(&submit)
+        );
+        assert_ne!(
+            ColorblindTheme.state_symbol_color(&cancel),
+            ColorblindTheme.state_symbol_color(&submit)
+        );
+    }
+
+    #[test]
+    fn error_and_warning_footers_have_distinct_symbols_and_colors() {
+        colors();
+        let error = ClackTheme.format_footer_error("Value is required");
+        let warning = ClackTheme.format_footer_warning("Value looks suspicious");
+
+        assert_eq!(
+            plain(&error),
+            format!("{S_BAR_END}  {S_ERROR} Value is required\n")
+        );
+        assert_eq!(
+            plain(&warning),
+            format!("{S_BAR_END}  {S_WARN} Value looks suspicious\n")
+        );
+        assert!(error.contains(&style(S_ERROR).red().to_string()));
+        assert!(warning.contains(&style(S_WARN).yellow().to_string()));
+        assert!(warning.contains(&style("Value looks suspicious").yellow().to_string()));
+    }
+
+    #[test]
+    fn warning_and_error_notes_use_their_symbols_and_colors() {
+        colors();
+        let warning = ClackTheme.format_note(&ThemeState::Error(String::new()), "Heads up", "Text");
+        let error = ClackTheme.format_note(&ThemeState::Cancel, "Failed", "Text");
+
+        assert!(plain(&warning).starts_with(&format!("{S_STEP_ERROR}  Heads up")));
+        assert!(plain(&error).starts_with(&format!("{S_STEP_CANCEL}  Failed")));
+        assert!(warning.starts_with(&style(S_STEP_ERROR).yellow().to_string()));
+        assert!(error.starts_with(&style(S_STEP_CANCEL).red().to_string()));
+        assert!(warning.contains(&style(S_CORNER_TOP_RIGHT).yellow().to_string()));
+        assert!(error.contains(&style(S_CORNER_TOP_RIGHT).red().to_string()));
+    }
+
+    #[test]
+    fn intro_subtitle_is_dimmed_within_the_frame() {
+        colors();
+        let intro = ClackTheme.format_intro_with_subtitle("create-app", "v1.2.0");
+
+        assert_eq!(
+            plain(&intro),
+            format!("{S_BAR_START}  create-app\n{S_BAR}  v1.2.0\n{S_BAR}\n")
+        );
+        assert!(intro.contains(&style("v1.2.0").dim().to_string()));
+        assert_eq!(
+            plain(&ClackTheme.format_intro("create-app")),
+            format!("{S_BAR_START}  create-app\n{S_BAR}\n")
+        );
+    }
+
+    #[test]
+    fn submitted_value_is_dimmed_unless_disabled() {
+        struct FullColor;
+        impl Theme for FullColor {
+            fn dim_submitted(&self) -> bool {
+                false
+            }
+        }
+
+        let submit = ThemeState::Submit;
+        assert_eq!(ClackTheme.input_style(&submit), Style::new().dim());
+        assert_eq!(FullColor.input_style(&submit), Style::new());
+        assert_eq!(
+            ClackTheme.checkbox_style(&submit, true, false),
+            Style::new().dim()
+        );
+        assert_eq!(FullColor.checkbox_style(&submit, true, false), Style::new());
+
+        colors();
+        let mut value = StringCursor::default();
+        value.extend("my-app");
+        let line = FullColor.format_input(&submit, &value);
+        assert!(!line.contains("\x1b[2m"), "{line:?}");
+        assert!(ClackTheme.format_input(&submit, &value).contains("\x1b[2m"));
+    }
+
+    #[test]
+    fn cursor_at_the_end_renders_the_visible_glyph() {
+        struct Underscore;
+        impl Theme for Underscore {
+            fn cursor_end_glyph(&self) -> String {
+                "_".into()
+            }
+        }
+
+        colors();
+        let plain_style = Style::new();
+        let reversed = |text: &str| style(text).reverse().to_string();
+
+        let empty = StringCursor::default();
+        assert_eq!(
+            ClackTheme.cursor_with_style(&empty, &plain_style),
+            reversed(" ")
+        );
+        assert_eq!(
+            Underscore.cursor_with_style(&empty, &plain_style),
+            reversed("_")
+        );
+
+        let mut typed = StringCursor::default();
+        typed.replace("app");
+        let line = Underscore.cursor_with_style(&typed, &plain_style);
+        assert!(line.ends_with(&reversed("_")), "{line:?}");
+        assert_eq!(plain(&line), "app_");
+
+        typed.move_left();
+        let line = Underscore.cursor_with_style(&typed, &plain_style);
+        assert!(
+            line.contains(&reversed("p")),
+            "the character under the cursor"
+        );
+        assert_eq!(plain(&line), "app");
+    }
+
+    #[test]
+    fn active_row_bar_uses_the_row_bar_color() {
+        struct Bright;
+        impl Theme for Bright {
+            fn row_bar_color(&self, state: &ThemeState, active: bool) -> Style {
+                match active {
+                    true => Style::new().white().bold(),
+                    false => self.bar_color(state),
+                }
+            }
+        }
+
+        colors();
+        let state = ThemeState::Active;
+        let bright = style(S_BAR).white().bold().to_string();
+        let cyan = Bright.bar_color(&state).apply_to(S_BAR).to_string();
+
+        let active = Bright.format_select_item(&state, true, "One", "");
+        let inactive = Bright.format_select_item(&state, false, "Two", "");
+        assert!(active.starts_with(&bright));
+        assert!(inactive.starts_with(&cyan));
+
+        let active = Bright.format_multiselect_item(&state, false, true, "One", "");
+        let inactive = Bright.format_multiselect_item(&state, false, false, "Two", "");
+        assert!(active.starts_with(&bright));
+        assert!(inactive.starts_with(&cyan));
+
+        let default = ClackTheme.format_select_item(&state, true, "One", "");
+        assert!(default.starts_with(&cyan));
+    }
+
+    #[test]
+    fn compact_theme_omits_the_spacer_lines() {
+        struct Compact;
+        impl Theme for Compact {
+            fn compact(&self) -> bool {
+                true
+            }
+        }
+
+        let is_spacer = |line: &str| line.trim_end() == S_BAR.to_string();
+        let render = |theme: &dyn Theme| {
+            plain(
+                &[
+                    theme.format_intro("create-app"),
+                    theme.format_note(&ThemeState::Active, "Next steps", "cd my-app"),
+                    theme.format_log("Done", &theme.info_symbol()),
+                    theme.format_footer(&ThemeState::Submit),
+                ]
+                .concat(),
+            )
+        };
+
+        let default = render(&ClackTheme);
+        assert!(default.lines().any(is_spacer));
+
+        let compact = render(&Compact);
+        assert!(!compact.lines().any(is_spacer));
+        assert!(
+            compact.lines().all(|line| !line.starts_with(' ')),
+            "the gutter is continuous"
+        );
+    }
+
+    #[test]
+    fn inline_header_is_the_header_without_the_newline() {
+        for state in [ThemeState::Active, ThemeState::Submit, ThemeState::Cancel] {
+            let header = ClackTheme.format_header(&state, "Name");
+            let inline = ClackTheme.format_header_inline(&state, "Name");
+
+            assert!(!inline.ends_with('\n'));
+            assert_eq!(header, inline + "\n");
+        }
+        assert!(
+            plain(&ClackTheme.format_header_inline(&ThemeState::Active, "Name"))
+                .ends_with("  Name")
+        );
+    }
+
+    #[test]
+    fn confirm_divider_is_rendered_between_the_options() {
+        struct Tilde;
+        impl Theme for Tilde {
+            fn format_confirm_divider(&self, state: &ThemeState) -> String {
+                match state {
+                    ThemeState::Active => " ~ ".into(),
+                    _ => String::new(),
+                }
+            }
+        }
+
+        let active = plain(&Tilde.format_confirm(&ThemeState::Active, true, false));
+        let (yes, no) = active.split_once(" ~ ").unwrap();
+        assert!(
+            yes.trim_end().ends_with("Yes") && no.trim_end().ends_with("No"),
+            "{active:?}"
+        );
+        assert!(!active.contains(" / "));
+
+        let default = plain(&ClackTheme.format_confirm(&ThemeState::Active, true, false));
+        assert!(default.contains(" / "), "{default:?}");
+
+        let submitted = plain(&Tilde.format_confirm(&ThemeState::Submit, true, false));
+        assert!(!submitted.contains(" ~ "), "{submitted:?}");
+    }
+
+    #[test]
+    fn adaptive_theme_branches_on_the_forced_background() {
+        let states = [ThemeState::Active, ThemeState::Submit];
+
+        set_terminal_background(TerminalBackground::Light);
+        assert_eq!(terminal_background(), TerminalBackground::Light);
+        for state in &states {
+            assert_ne!(AdaptiveTheme.bar_color(state), ClackTheme.bar_color(state));
+        }
+        assert_ne!(
+            AdaptiveTheme.state_symbol_color(&ThemeState::Submit),
+            ClackTheme.state_symbol_color(&ThemeState::Submit)
+        );
+
+        set_terminal_background(TerminalBackground::Dark);
+        assert_eq!(terminal_background(), TerminalBackground::Dark);
+        for state in &states {
+            assert_eq!(AdaptiveTheme.bar_color(state), ClackTheme.bar_color(state));
+        }
+        TERMINAL_BACKGROUND.store(0, Ordering::Relaxed);
+    }
+
+    #[test]
+    fn cancel_symbol_precedes_the_cancellation_message() {
+        struct Stop;
+        impl Theme for Stop {
+            fn cancel_symbol(&self) -> String {
+                "#".into()
+            }
+        }
+
+        let footer = plain(&Stop.format_footer(&ThemeState::Cancel));
+        assert_eq!(footer, format!("{S_BAR_END}  # Operation cancelled.\n"));
+
+        let default = plain(&ClackTheme.format_footer(&ThemeState::Cancel));
+        assert_eq!(default, format!("{S_BAR_END}  Operation cancelled.\n"));
+    }
+
+    #[test]
+    fn nested_note_is_placed_inside_the_outer_bars() {
+        let state = ThemeState::Submit;
+        let note = plain(&ClackTheme.format_note(&state, "Warning", "The cache is cold"));
+        let nested =
+            plain(&ClackTheme.format_note_nested(&state, 1, "Warning", "The cache is cold"));
+
+        assert_eq!(nested.lines().count(), note.lines().count());
+        for (nested, line) in nested.lines().zip(note.lines()) {
+            assert_eq!(nested, format!("{S_BAR}  {line}"));
+        }
+        assert_eq!(
+            ClackTheme.format_note_nested(&state, 0, "Warning", "Text"),
+            ClackTheme.format_note(&state, "Warning", "Text")
+        );
+    }
+
+    #[test]
+    fn multiline_header_continues_with_the_gutter() {
+        for state in [ThemeState::Active, ThemeState::Submit] {
+            let header = plain(&ClackTheme.format_header(&state, "Project name\n(used in logs)"));
+            let lines = header.lines().collect::<Vec<_>>();
+
+            assert_eq!(lines.len(), 2);
+            assert!(lines[0].ends_with("  Project name"));
+            assert_eq!(lines[1], format!("{S_BAR}  (used in logs)"));
+        }
+    }
+
+    #[test]
+    fn spinner_elapsed_is_compact() {
+        let format = |secs: f64| {
+            let elapsed = ClackTheme.format_spinner_elapsed(Duration::from_secs_f64(secs));
+            console::strip_ansi_codes(&elapsed).to_string()
+        };
+
+        assert_eq!(format(3.25), "(3.2s)");
+        assert_eq!(format(65.0), "(1m 5s)");
+        assert_eq!(format(3725.0), "(1h 2m)");
+    }
+
+    #[test]
+    fn spinner_error_stop_follows_the_theme_colors() {
+        colors();
+        let state = ThemeState::Error("Failed".into());
+        let clack = ClackTheme.format_spinner_stop(&state, "Failed");
+        let colorblind = ColorblindTheme.format_spinner_stop(&state, "Failed");
+
+        assert!(plain(&clack).starts_with(&format!("{S_STEP_ERROR}  Failed")));
+        assert!(clack.starts_with(&ClackTheme.state_symbol(&state)));
+        assert!(clack.contains(&ClackTheme.bar_color(&state).apply_to("Failed").to_string()));
+        let orange = Style::new().color256(214).bold();
+        assert!(colorblind.starts_with(&orange.apply_to(S_STEP_ERROR).to_string()));
+        assert!(colorblind.contains(&orange.apply_to("Failed").to_string()));
+    }
+
+    #[test]
+    fn select_overflow_lines_count_the_hidden_items() {
+        let state = ThemeState::Active;
+        let (above, below) = ClackTheme.format_select_overflow(&state, 2, 5);
+        assert_eq!(plain(&above), format!("{S_BAR}  {S_ARROW_UP} 2 more\n"));
+        assert_eq!(plain(&below), format!("{S_BAR}  {S_ARROW_DOWN} 5 more\n"));
+
+        let (above, below) = ClackTheme.format_select_overflow(&state, 0, 1);
+        assert!(above.is_empty(), "nothing is hidden above");
+        assert_eq!(plain(&below), format!("{S_BAR}  {S_ARROW_DOWN} 1 more\n"));
+    }
+}